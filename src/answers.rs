@@ -0,0 +1,248 @@
+use std::fs;
+use std::path::Path;
+
+use crate::solution::Part;
+
+// Name of the answer file within a data directory.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn by_name() {
+        let answers = Answers::parse("7 day7.txt 208 1664").expect("Valid answers rejected");
+
+        assert_eq!(Some("208"), answers.expected(7, Some("day7.txt"), "", Part::One));
+        assert_eq!(Some("1664"), answers.expected(7, Some("day7.txt"), "", Part::Two));
+    }
+
+    #[test]
+    fn by_hash() {
+        let input = "1-3 a: abcde\n";
+        let answers = Answers::parse(&format!("2 {} 1 0", key(input))).expect("Valid answers rejected");
+
+        assert_eq!(Some("1"), answers.expected(2, None, input, Part::One));
+        assert_eq!(Some("0"), answers.expected(2, Some("mine.txt"), input, Part::Two));
+    }
+
+    // A matching hash is preferred to a matching name.
+    #[test]
+    fn hash_before_name() {
+        let input = "0,3,6";
+        let answers = format!("15 day15.txt 240 505\n15 {} 436 175594", key(input));
+        let answers = Answers::parse(&answers).expect("Valid answers rejected");
+
+        assert_eq!(Some("436"), answers.expected(15, Some("day15.txt"), input, Part::One));
+        assert_eq!(Some("240"), answers.expected(15, Some("day15.txt"), "14,8,16,0,1,17", Part::One));
+    }
+
+    #[test]
+    fn missing() {
+        let answers = Answers::parse("13 day13.txt 2238 -").expect("Valid answers rejected");
+
+        assert_eq!(Some("2238"), answers.expected(13, Some("day13.txt"), "", Part::One));
+        assert_eq!(None, answers.expected(13, Some("day13.txt"), "", Part::Two));
+        assert_eq!(None, answers.expected(12, Some("day13.txt"), "", Part::One));
+        assert_eq!(None, answers.expected(13, Some("day12.txt"), "", Part::One));
+    }
+
+    #[test]
+    fn comments_and_blank_lines() {
+        let answers = "
+        # day  input     part 1  part 2
+
+        1      day1.txt  514579  241861950 # the example
+        ";
+
+        let answers = Answers::parse(answers).expect("Valid answers rejected");
+        assert_eq!(Some("241861950"), answers.expected(1, Some("day1.txt"), "", Part::Two));
+    }
+
+    #[test]
+    fn wrong_number_of_fields() {
+        let err = Answers::parse("1 day1.txt 1 2\n2 day2.txt 3").err().expect("No error returned.");
+        assert_eq!("Line 2: expected <day> <input> <part 1> <part 2>", err);
+    }
+
+    #[test]
+    fn invalid_day() {
+        let err = Answers::parse("one day1.txt 1 2").err().expect("No error returned.");
+        assert_eq!("Line 1: invalid day: one", err);
+    }
+
+    #[test]
+    fn invalid_hash() {
+        let err = Answers::parse("1 fnv:xyz 1 2").err().expect("No error returned.");
+        assert_eq!("Line 1: invalid hash: fnv:xyz", err);
+    }
+}
+
+#[cfg(test)]
+mod test_hash {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(0xcbf29ce484222325, hash(""));
+        assert_eq!(0xaf63dc4c8601ec8c, hash("a"));
+    }
+
+    // Line endings and whitespace at the end don't change the hash.
+    #[test]
+    fn normalised() {
+        assert_eq!(hash("abc\ndef"), hash("abc\r\ndef\r\n"));
+        assert_eq!(hash("abc\ndef"), hash("abc\ndef\n\n"));
+        assert_ne!(hash("abc\ndef"), hash("abc\n\ndef"));
+        assert_ne!(hash("abc\ndef"), hash("abc \ndef"));
+    }
+
+    #[test]
+    fn key_format() {
+        assert_eq!("fnv:cbf29ce484222325", key(""));
+    }
+}
+
+// How an entry in the answer file identifies an input.
+enum Input {
+    Name(String),
+    Hash(u64)
+}
+
+struct Entry {
+    day: u32,
+    input: Input,
+    part1: Option<String>,
+    part2: Option<String>
+}
+
+// Known answers for each day's puzzle inputs.
+//
+// The answer file has one line per input:
+// <day> <input> <part 1> <part 2>
+//
+// Where:
+// - <input> is either the input's file name, or its hash as
+//   given by key(), e.g. fnv:0123456789abcdef
+// - <part 1> and <part 2> are the expected answers, or "-" if unknown
+//
+// Blank lines, and anything after a '#', are ignored.
+pub struct Answers {
+    entries: Vec<Entry>
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Answers, String> {
+        let mut entries = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            // Strip comments.
+            let line = match line.find('#') {
+                Some(index) => &line[..index],
+                None => line
+            };
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            if fields.is_empty() {
+                continue;
+            }
+
+            if fields.len() != 4 {
+                return Err(format!("Line {}: expected <day> <input> <part 1> <part 2>", i + 1));
+            }
+
+            let day = match fields[0].parse() {
+                Ok(d) => d,
+                Err(_) => return Err(format!("Line {}: invalid day: {}", i + 1, fields[0]))
+            };
+
+            let input = match fields[1].strip_prefix("fnv:") {
+                Some(hex) => match u64::from_str_radix(hex, 16) {
+                    Ok(h) => Input::Hash(h),
+                    Err(_) => return Err(format!("Line {}: invalid hash: {}", i + 1, fields[1]))
+                },
+                None => Input::Name(String::from(fields[1]))
+            };
+
+            let answer = |s: &str| {
+                if s == "-" {
+                    None
+                } else {
+                    Some(String::from(s))
+                }
+            };
+
+            entries.push(Entry {
+                day,
+                input,
+                part1: answer(fields[2]),
+                part2: answer(fields[3])
+            });
+        }
+
+        return Ok(Answers { entries });
+    }
+
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => return Err(format!("Could not read {}: {}", path.display(), e))
+        };
+
+        return match Answers::parse(&s) {
+            Ok(a) => Ok(a),
+            Err(e) => Err(format!("{}: {}", path.display(), e))
+        };
+    }
+
+    // Returns the expected answer for a part of a given day's input.
+    //
+    // An entry matching the input's hash is preferred, falling back
+    // to one matching the input's file name if there is one.
+    pub fn expected(&self, day: u32, name: Option<&str>, input: &str, part: Part) -> Option<&str> {
+        let input_hash = hash(input);
+
+        let by_hash = self.entries.iter().find(|e| {
+            e.day == day && matches!(e.input, Input::Hash(h) if h == input_hash)
+        });
+
+        let by_name = self.entries.iter().find(|e| {
+            e.day == day && matches!(&e.input, Input::Name(n) if Some(n.as_str()) == name)
+        });
+
+        let entry = by_hash.or(by_name)?;
+
+        let answer = match part {
+            Part::One => &entry.part1,
+            Part::Two => &entry.part2
+        };
+
+        return answer.as_deref();
+    }
+}
+
+// 64-bit FNV-1a hash of an input.
+//
+// Line endings are normalised and whitespace at the very end is
+// ignored, so that the same input checked out on different platforms
+// has the same hash. Whitespace at the end of each line still counts.
+pub fn hash(input: &str) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+
+    let lines: Vec<&str> = input.trim_end().lines().collect();
+    let normalised = lines.join("\n");
+
+    for b in normalised.bytes() {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+
+    return h;
+}
+
+// The key identifying an input by hash in the answer file.
+pub fn key(input: &str) -> String {
+    return format!("fnv:{:016x}", hash(input));
+}
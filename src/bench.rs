@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::error;
+use crate::memory::{self, Usage};
+use crate::solution::{Part, Puzzle};
+use crate::table::{self, Align};
+
+#[cfg(test)]
+mod test_timing {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        return Duration::from_millis(n);
+    }
+
+    #[test]
+    fn odd_samples() {
+        let timing = Timing::from_samples(vec![ms(5), ms(1), ms(3)]);
+
+        assert_eq!(ms(1), timing.min);
+        assert_eq!(ms(3), timing.median);
+        assert_eq!(ms(5), timing.max);
+    }
+
+    // With an even number of samples, the median is halfway
+    // between the middle two.
+    #[test]
+    fn even_samples() {
+        let timing = Timing::from_samples(vec![ms(8), ms(2), ms(4), ms(1)]);
+
+        assert_eq!(ms(1), timing.min);
+        assert_eq!(ms(3), timing.median);
+        assert_eq!(ms(8), timing.max);
+    }
+
+    #[test]
+    fn single_sample() {
+        let timing = Timing::from_samples(vec![ms(7)]);
+
+        assert_eq!(ms(7), timing.min);
+        assert_eq!(ms(7), timing.median);
+        assert_eq!(ms(7), timing.max);
+    }
+
+    #[test]
+    fn time_runs() {
+        let mut count = 0;
+        time(4, || count += 1);
+
+        assert_eq!(4, count);
+    }
+}
+
+#[cfg(test)]
+mod test_baseline {
+    use super::*;
+
+    fn measurement(day: u32, step: Step, median: u64) -> Measurement {
+        let median = Duration::from_nanos(median);
+
+        return Measurement {
+            day,
+            step,
+            timing: Timing { min: median, median, max: median },
+            memory: None
+        };
+    }
+
+    #[test]
+    fn round_trip() {
+        let measurements = vec![
+            measurement(7, Step::Parse, 1500),
+            measurement(7, Step::Solve(Part::One), 20000),
+            measurement(15, Step::Solve(Part::Two), 3000000000)
+        ];
+
+        let baseline = Baseline::parse(&Baseline::format(&measurements)).expect("Saved baseline rejected");
+
+        assert_eq!(Some(Duration::from_nanos(1500)), baseline.median(7, Step::Parse));
+        assert_eq!(Some(Duration::from_nanos(20000)), baseline.median(7, Step::Solve(Part::One)));
+        assert_eq!(Some(Duration::from_secs(3)), baseline.median(15, Step::Solve(Part::Two)));
+
+        assert_eq!(None, baseline.median(7, Step::Solve(Part::Two)));
+        assert_eq!(None, baseline.median(8, Step::Parse));
+    }
+
+    #[test]
+    fn invalid_step() {
+        let err = Baseline::parse("7 part3 100").err().expect("No error returned.");
+        assert_eq!("Line 1: invalid step: part3", err);
+    }
+
+    #[test]
+    fn invalid_line() {
+        let err = Baseline::parse("# comment\n7 parse").err().expect("No error returned.");
+        assert_eq!("Line 2: expected <day> <step> <median ns>", err);
+    }
+
+    #[test]
+    fn change() {
+        let baseline = Baseline::parse("7 parse 1000\n7 part1 1000").unwrap();
+
+        let measurements = vec![
+            measurement(7, Step::Parse, 1500),
+            measurement(7, Step::Solve(Part::One), 900),
+            measurement(7, Step::Solve(Part::Two), 900)
+        ];
+
+        let table = table(&measurements, Some(&baseline));
+
+        assert_eq!("\
+Day  Step    Min     Median  Max     Baseline  Change
+  7  parse   1.50µs  1.50µs  1.50µs  1.00µs    +50.0%
+  7  part 1  900ns   900ns   900ns   1.00µs    -10.0%
+  7  part 2  900ns   900ns   900ns   -         -
+", table);
+    }
+}
+
+#[cfg(test)]
+mod test_memory {
+    use super::*;
+
+    #[test]
+    fn table_columns() {
+        let timing = Timing::from_samples(vec![Duration::from_micros(5)]);
+
+        let measurements = vec![
+            Measurement {
+                day: 15,
+                step: Step::Parse,
+                timing,
+                memory: Some(Usage { allocations: 3, allocated: 96, peak: 64 })
+            },
+            Measurement {
+                day: 15,
+                step: Step::Solve(Part::Two),
+                timing,
+                memory: Some(Usage { allocations: 1042, allocated: 1536 * 1024 * 1024, peak: 768 * 1024 * 1024 })
+            }
+        ];
+
+        assert_eq!("\
+Day  Step    Min     Median  Max     Allocs  Allocated  Peak
+ 15  parse   5.00µs  5.00µs  5.00µs       3  96B        64B
+ 15  part 2  5.00µs  5.00µs  5.00µs    1042  1.50GiB    768.00MiB
+", table(&measurements, None));
+    }
+
+    // Without the counting allocator, nothing is counted.
+    #[test]
+    fn not_counted() {
+        let measurements = bench(crate::days::get(1).unwrap().as_ref(), "1721\n299\n979\n366\n675\n1456\n", &[Part::One], 1).unwrap();
+        assert!(measurements.iter().all(|m| m.memory.is_none()));
+    }
+}
+
+#[cfg(test)]
+mod test_format_duration {
+    use super::*;
+
+    #[test]
+    fn units() {
+        assert_eq!("999ns", format_duration(Duration::from_nanos(999)));
+        assert_eq!("1.00µs", format_duration(Duration::from_nanos(1000)));
+        assert_eq!("12.35ms", format_duration(Duration::from_micros(12345)));
+        assert_eq!("2.50s", format_duration(Duration::from_millis(2500)));
+    }
+}
+
+// A step of solving a puzzle which is timed separately.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Step {
+    Parse,
+    Solve(Part)
+}
+
+impl Step {
+    // The name of this step in a baseline file.
+    fn key(&self) -> &'static str {
+        return match self {
+            Step::Parse => "parse",
+            Step::Solve(Part::One) => "part1",
+            Step::Solve(Part::Two) => "part2"
+        };
+    }
+
+    fn from_key(s: &str) -> Option<Step> {
+        return match s {
+            "parse" => Some(Step::Parse),
+            "part1" => Some(Step::Solve(Part::One)),
+            "part2" => Some(Step::Solve(Part::Two)),
+            _ => None
+        };
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Solve(part) => write!(f, "part {}", part)
+        }
+    }
+}
+
+// Summary of the wall time taken over several runs.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration
+}
+
+impl Timing {
+    // Summarises a non-empty set of samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Timing {
+        assert!(!samples.is_empty());
+
+        samples.sort();
+
+        let n = samples.len();
+
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+
+        return Timing {
+            min: samples[0],
+            median,
+            max: samples[n - 1]
+        };
+    }
+}
+
+// Runs a function the given number of times (at least once),
+// timing each run.
+pub fn time<T, F>(runs: usize, mut f: F) -> Timing
+    where F: FnMut() -> T {
+    let mut samples = Vec::new();
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+
+    return Timing::from_samples(samples);
+}
+
+pub struct Measurement {
+    pub day: u32,
+    pub step: Step,
+    pub timing: Timing,
+
+    // What one run allocated, if allocations are being counted.
+    pub memory: Option<Usage>
+}
+
+// Runs a function once more to see what it allocates,
+// if allocations are being counted.
+fn allocations<T, F: FnOnce() -> T>(f: F) -> Option<Usage> {
+    if !memory::counting() {
+        return None;
+    }
+
+    let (result, usage) = memory::measure(f);
+    drop(black_box(result));
+
+    return Some(usage);
+}
+
+// Times parsing the input and solving each of the given parts,
+// running each step the given number of times.
+//
+// Stops at the first step which fails.
+//
+// When allocations are being counted, each step is also run
+// once more on its own to count what it allocates.
+pub fn bench(puzzle: &dyn Puzzle, input: &str, parts: &[Part], runs: usize) -> error::Result<Vec<Measurement>> {
+    let day = puzzle.day();
+
+    let mut measurements = vec![Measurement {
+        day,
+        step: Step::Parse,
+        timing: time(runs, || puzzle.parse(input)),
+        memory: allocations(|| puzzle.parse(input))
+    }];
+
+    let parsed = puzzle.parse(input)?;
+
+    for &part in parts {
+        let mut answer = None;
+        let timing = time(runs, || answer = Some(parsed.solve(part)));
+
+        // Every run gives the same answer, so checking
+        // the last one is enough.
+        answer.unwrap()?;
+
+        measurements.push(Measurement {
+            day,
+            step: Step::Solve(part),
+            timing,
+            memory: allocations(|| parsed.solve(part))
+        });
+    }
+
+    return Ok(measurements);
+}
+
+// Median timings saved from a previous benchmark, to compare against.
+//
+// A baseline file has one line per step:
+// <day> <step> <median ns>
+//
+// Where <step> is one of "parse", "part1" or "part2".
+// Blank lines, and lines starting with '#', are ignored.
+pub struct Baseline {
+    medians: HashMap<(u32, Step), Duration>
+}
+
+impl Baseline {
+    pub fn parse(s: &str) -> Result<Baseline, String> {
+        let mut medians = HashMap::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            if fields.len() != 3 {
+                return Err(format!("Line {}: expected <day> <step> <median ns>", i + 1));
+            }
+
+            let day = match fields[0].parse() {
+                Ok(d) => d,
+                Err(_) => return Err(format!("Line {}: invalid day: {}", i + 1, fields[0]))
+            };
+
+            let step = match Step::from_key(fields[1]) {
+                Some(s) => s,
+                None => return Err(format!("Line {}: invalid step: {}", i + 1, fields[1]))
+            };
+
+            let median = match fields[2].parse() {
+                Ok(n) => Duration::from_nanos(n),
+                Err(_) => return Err(format!("Line {}: invalid time: {}", i + 1, fields[2]))
+            };
+
+            medians.insert((day, step), median);
+        }
+
+        return Ok(Baseline { medians });
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => return Err(format!("Could not read {}: {}", path.display(), e))
+        };
+
+        return match Baseline::parse(&s) {
+            Ok(b) => Ok(b),
+            Err(e) => Err(format!("{}: {}", path.display(), e))
+        };
+    }
+
+    // Formats the medians of a set of measurements as a baseline file.
+    pub fn format(measurements: &[Measurement]) -> String {
+        let mut s = String::from("# <day> <step> <median ns>\n");
+
+        for m in measurements {
+            s.push_str(&format!("{} {} {}\n", m.day, m.step.key(), m.timing.median.as_nanos()));
+        }
+
+        return s;
+    }
+
+    pub fn median(&self, day: u32, step: Step) -> Option<Duration> {
+        return self.medians.get(&(day, step)).copied();
+    }
+}
+
+// Formats a duration to three significant-ish figures,
+// in the largest unit that keeps it above 1.
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+
+    if ns < 1000 {
+        return format!("{}ns", ns);
+    } else if ns < 1_000_000 {
+        return format!("{:.2}µs", ns as f64 / 1e3);
+    } else if ns < 1_000_000_000 {
+        return format!("{:.2}ms", ns as f64 / 1e6);
+    } else {
+        return format!("{:.2}s", ns as f64 / 1e9);
+    }
+}
+
+// Formats measurements as a table, one row per step, comparing
+// each median against the baseline if there is one.
+pub fn table(measurements: &[Measurement], baseline: Option<&Baseline>) -> String {
+    let mut columns = vec![
+        ("Day", Align::Right),
+        ("Step", Align::Left),
+        ("Min", Align::Left),
+        ("Median", Align::Left),
+        ("Max", Align::Left)
+    ];
+
+    if baseline.is_some() {
+        columns.push(("Baseline", Align::Left));
+        columns.push(("Change", Align::Left));
+    }
+
+    let counted = measurements.iter().any(|m| m.memory.is_some());
+
+    if counted {
+        columns.push(("Allocs", Align::Right));
+        columns.push(("Allocated", Align::Left));
+        columns.push(("Peak", Align::Left));
+    }
+
+    let mut rows = Vec::new();
+
+    for m in measurements {
+        let mut row = vec![
+            m.day.to_string(),
+            m.step.to_string(),
+            format_duration(m.timing.min),
+            format_duration(m.timing.median),
+            format_duration(m.timing.max)
+        ];
+
+        if let Some(baseline) = baseline {
+            match baseline.median(m.day, m.step) {
+                Some(before) => {
+                    let after = m.timing.median.as_secs_f64();
+                    let change = (after - before.as_secs_f64()) / before.as_secs_f64() * 100.0;
+
+                    row.push(format_duration(before));
+                    row.push(format!("{:+.1}%", change));
+                },
+                None => {
+                    row.push(String::from("-"));
+                    row.push(String::from("-"));
+                }
+            }
+        }
+
+        if counted {
+            match m.memory {
+                Some(usage) => {
+                    row.push(usage.allocations.to_string());
+                    row.push(memory::format_bytes(usage.allocated));
+                    row.push(memory::format_bytes(usage.peak));
+                },
+                None => {
+                    row.extend(vec![String::from("-"); 3]);
+                }
+            }
+        }
+
+        rows.push(row);
+    }
+
+    return table::render(&columns, &rows);
+}
@@ -0,0 +1,1271 @@
+// Advent of Code 2020
+// Runner for every day's solution.
+
+// Explicit returns, assert_eq!(true, ...) and tests at the top
+// of each file are used throughout.
+#![allow(clippy::needless_return, clippy::bool_assert_comparison, clippy::items_after_test_module)]
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aoc::answers::{self, Answers};
+use aoc::bench::{self, Baseline};
+use aoc::days::{self, day2::{self, Day2}};
+use aoc::debug::Debugger;
+use aoc::error::Error;
+use aoc::export::{self, Gif, ImageFormat, Images};
+use aoc::gen;
+use aoc::input::Source;
+use aoc::parallel::{self, Unfinished};
+use aoc::report::{Format, Record, Status as RecordStatus};
+use aoc::scaffold;
+use aoc::solution::{Parsed, Part, Solution};
+use aoc::verify::{self, Status};
+use aoc::viz::{self, Headless, Renderer, Terminal, Viewport};
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: aoc::memory::CountingAllocator = aoc::memory::CountingAllocator;
+
+const USAGE: &str = "Usage: aoc <day>... [--part <1|2>] [--input <file|->] [--data-dir <dir>]
+                 [--format <text|json|csv>] [--jobs <n>] [--timeout <seconds|none>]
+       aoc all [--part <1|2>] [--data-dir <dir>] [--format <text|json|csv>]
+                 [--jobs <n>] [--timeout <seconds|none>]
+       aoc verify [<day>...|all] [--part <1|2>] [--data-dir <dir>] [--answers <file>]
+       aoc bench [<day>...|all] [--part <1|2>] [--data-dir <dir>] [--runs <n>]
+                 [--baseline <file>] [--save-baseline <file>]
+       aoc gen <day> [--seed <n>] [--size <n>]
+       aoc new <day>
+       aoc viz <day> [--part <1|2>] [--input <file|->] [--data-dir <dir>]
+                 [--fps <n|none>] [--headless]
+                 [--output <dir> [--image <gif|ppm|pgm|svg>] [--scale <n>]]
+       aoc debug <day> [--part <1|2>] [--input <file>] [--data-dir <dir>]
+       aoc passwords [--policy <policy>]... [--input <file|->] [--data-dir <dir>]
+
+Input is read from <dir>/dayN.txt, where <dir> defaults to $AOC_DATA_DIR,
+or data/ if that is not set. --input reads a single day's input from a file,
+or from standard input if the file is '-'.
+
+--format prints one record per day and part, with the answer, status,
+time taken and input hash, as JSON lines or CSV. The default is text.
+
+Days are run on <n> threads at once (default one per CPU), and printed in
+order. A part which takes longer than --timeout (default 60 seconds) is
+reported as timed out.
+
+verify checks answers against <dir>/answers.txt, or the file given by
+--answers. It checks every day if none are given.
+
+bench times parsing and each part over <n> runs (default 10), and reports
+the min, median and max. --save-baseline writes the medians to a file, which
+a later run can be compared against with --baseline. When built with the
+count-allocations feature, it also shows the allocations made by one run of
+each step, the bytes they asked for, and the peak heap size.
+
+gen prints a random input for a day, the same for the same --seed (default 0).
+What --size (default 100) means depends on the day, e.g. lines or grid width.
+
+new sets up a day that hasn't been started: its module in src/days, registered
+with the runner, empty data and example files, and placeholder tests. It must
+be run from the crate's root directory.
+
+viz draws each step of solving a day (3, 11 or 12) in the terminal, at up to
+--fps frames per second (default 10). --headless draws nothing, and just
+counts the frames. --output saves the frames in <dir> instead, as an animated
+GIF (the default) at the --fps frame rate, or as one PPM, PGM or SVG file per
+frame. Each character is drawn as a square of --scale pixels (default 4).
+
+debug steps through solving a day (8, 11 or 15) a tick at a time, reading
+commands from standard input: step, jump <n>, run [<condition>], show, help
+and quit. Each part is debugged in turn.
+
+passwords counts the day 2 passwords valid under each --policy, in one pass
+(default: sled and toboggan, the policies of parts 1 and 2). Policies can be
+combined with and, or, not and brackets, e.g. 'sled and not toboggan'.";
+
+#[cfg(test)]
+mod test_parse_args {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        return s.split_whitespace().map(String::from).collect();
+    }
+
+    // Every day solved so far, however many that is.
+    fn solved_days() -> Vec<u32> {
+        return days::all().iter().map(|p| p.day()).collect();
+    }
+
+    #[test]
+    fn single_day() {
+        let options = parse_args(&args("7")).expect("Valid arguments rejected");
+
+        assert_eq!(vec![7], options.days);
+        assert_eq!(vec![Part::One, Part::Two], options.parts);
+        assert_eq!(Source::default(), options.source);
+    }
+
+    #[test]
+    fn single_day_single_part() {
+        let options = parse_args(&args("7 --part 2")).expect("Valid arguments rejected");
+
+        assert_eq!(vec![7], options.days);
+        assert_eq!(vec![Part::Two], options.parts);
+    }
+
+    #[test]
+    fn multiple_days() {
+        let options = parse_args(&args("--part 1 3 12")).expect("Valid arguments rejected");
+
+        assert_eq!(vec![3, 12], options.days);
+        assert_eq!(vec![Part::One], options.parts);
+    }
+
+    #[test]
+    fn all_days() {
+        let options = parse_args(&args("all")).expect("Valid arguments rejected");
+
+        assert_eq!(solved_days(), options.days);
+    }
+
+    #[test]
+    fn no_days() {
+        let err = parse_args(&args("--part 1")).err().expect("No error returned.");
+        assert_eq!("No days given.", err);
+    }
+
+    #[test]
+    fn invalid_part() {
+        let err = parse_args(&args("7 --part 3")).err().expect("No error returned.");
+        assert_eq!("Invalid part: 3", err);
+    }
+
+    #[test]
+    fn missing_part() {
+        let err = parse_args(&args("7 --part")).err().expect("No error returned.");
+        assert_eq!("--part requires a value.", err);
+    }
+
+    #[test]
+    fn unknown_day() {
+        let err = parse_args(&args("26")).err().expect("No error returned.");
+        assert_eq!("No solution for day 26.", err);
+    }
+
+    #[test]
+    fn not_a_day() {
+        let err = parse_args(&args("seven")).err().expect("No error returned.");
+        assert_eq!("Invalid day: seven", err);
+    }
+
+    #[test]
+    fn input_file() {
+        let options = parse_args(&args("9 --input mine/day9.txt")).expect("Valid arguments rejected");
+        assert_eq!(Source::File(PathBuf::from("mine/day9.txt")), options.source);
+    }
+
+    #[test]
+    fn input_stdin() {
+        let options = parse_args(&args("--input - 9")).expect("Valid arguments rejected");
+        assert_eq!(Source::Stdin, options.source);
+    }
+
+    #[test]
+    fn input_multiple_days() {
+        let err = parse_args(&args("all --input mine/day9.txt")).err().expect("No error returned.");
+        assert_eq!("--input can only be used with a single day.", err);
+    }
+
+    #[test]
+    fn data_dir() {
+        let options = parse_args(&args("all --data-dir ci/fixtures")).expect("Valid arguments rejected");
+        assert_eq!(Source::DataDir(PathBuf::from("ci/fixtures")), options.source);
+    }
+
+    #[test]
+    fn verify_all() {
+        let options = parse_args(&args("verify")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Verify, options.mode);
+        assert_eq!(solved_days(), options.days);
+        assert_eq!(None, options.answers);
+    }
+
+    #[test]
+    fn verify_days() {
+        let options = parse_args(&args("verify 4 6 --answers mine.txt")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Verify, options.mode);
+        assert_eq!(vec![4, 6], options.days);
+        assert_eq!(Some(PathBuf::from("mine.txt")), options.answers);
+    }
+
+    #[test]
+    fn bench() {
+        let options = parse_args(&args("bench 15 --part 2")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Bench, options.mode);
+        assert_eq!(vec![15], options.days);
+        assert_eq!(vec![Part::Two], options.parts);
+        assert_eq!(DEFAULT_RUNS, options.runs);
+    }
+
+    #[test]
+    fn bench_options() {
+        let options = parse_args(&args("bench --runs 3 --baseline old.txt --save-baseline new.txt"))
+            .expect("Valid arguments rejected");
+
+        assert_eq!(solved_days(), options.days);
+        assert_eq!(3, options.runs);
+        assert_eq!(Some(PathBuf::from("old.txt")), options.baseline);
+        assert_eq!(Some(PathBuf::from("new.txt")), options.save_baseline);
+    }
+
+    #[test]
+    fn invalid_runs() {
+        let err = parse_args(&args("bench --runs 0")).err().expect("No error returned.");
+        assert_eq!("Invalid number of runs: 0", err);
+    }
+
+    #[test]
+    fn format() {
+        let options = parse_args(&args("all --format json")).expect("Valid arguments rejected");
+        assert_eq!(Format::Json, options.format);
+
+        let options = parse_args(&args("all")).expect("Valid arguments rejected");
+        assert_eq!(Format::Text, options.format);
+    }
+
+    #[test]
+    fn invalid_format() {
+        let err = parse_args(&args("all --format xml")).err().expect("No error returned.");
+        assert_eq!("Invalid format: xml", err);
+    }
+
+    #[test]
+    fn format_only_when_running() {
+        let err = parse_args(&args("verify --format csv")).err().expect("No error returned.");
+        assert_eq!("--format can only be used when running days.", err);
+    }
+
+    #[test]
+    fn jobs_and_timeout() {
+        let options = parse_args(&args("all --jobs 2 --timeout 1.5")).expect("Valid arguments rejected");
+
+        assert_eq!(2, options.jobs);
+        assert_eq!(Some(Duration::from_millis(1500)), options.timeout);
+    }
+
+    #[test]
+    fn default_timeout() {
+        let options = parse_args(&args("all")).expect("Valid arguments rejected");
+        assert_eq!(Some(DEFAULT_TIMEOUT), options.timeout);
+
+        let options = parse_args(&args("all --timeout none")).expect("Valid arguments rejected");
+        assert_eq!(None, options.timeout);
+    }
+
+    #[test]
+    fn invalid_jobs() {
+        let err = parse_args(&args("all --jobs 0")).err().expect("No error returned.");
+        assert_eq!("Invalid number of jobs: 0", err);
+    }
+
+    #[test]
+    fn invalid_timeout() {
+        let err = parse_args(&args("all --timeout -1")).err().expect("No error returned.");
+        assert_eq!("Invalid timeout: -1", err);
+    }
+
+    #[test]
+    fn jobs_only_when_running() {
+        let err = parse_args(&args("bench --jobs 4")).err().expect("No error returned.");
+        assert_eq!("--jobs can only be used when running days.", err);
+    }
+
+    #[test]
+    fn gen() {
+        let options = parse_args(&args("gen 7 --seed 12 --size 40")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Gen, options.mode);
+        assert_eq!(vec![7], options.days);
+        assert_eq!(12, options.seed);
+        assert_eq!(40, options.size);
+
+        let options = parse_args(&args("gen 7")).expect("Valid arguments rejected");
+        assert_eq!((0, DEFAULT_SIZE), (options.seed, options.size));
+    }
+
+    #[test]
+    fn gen_single_day() {
+        let err = parse_args(&args("gen")).err().expect("No error returned.");
+        assert_eq!("gen requires a single day.", err);
+
+        let err = parse_args(&args("gen 1 2")).err().expect("No error returned.");
+        assert_eq!("gen requires a single day.", err);
+    }
+
+    #[test]
+    fn seed_only_when_generating() {
+        let err = parse_args(&args("4 --seed 1")).err().expect("No error returned.");
+        assert_eq!("--seed can only be used when generating input.", err);
+    }
+
+    #[test]
+    fn new() {
+        let options = parse_args(&args("new 16")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::New, options.mode);
+        assert_eq!(vec![16], options.days);
+
+        let err = parse_args(&args("new 26")).err().expect("No error returned.");
+        assert_eq!("Invalid day: 26", err);
+
+        let err = parse_args(&args("new")).err().expect("No error returned.");
+        assert_eq!("new requires a single day.", err);
+    }
+
+    #[test]
+    fn viz() {
+        let options = parse_args(&args("viz 11 --part 2 --fps 30")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Viz, options.mode);
+        assert_eq!(vec![11], options.days);
+        assert_eq!(Some(30.0), options.fps);
+        assert_eq!(false, options.headless);
+
+        let options = parse_args(&args("viz 3 --fps none --headless")).expect("Valid arguments rejected");
+        assert_eq!(None, options.fps);
+        assert_eq!(true, options.headless);
+    }
+
+    #[test]
+    fn viz_output() {
+        let options = parse_args(&args("viz 12 --output frames --image svg --scale 2")).expect("Valid arguments rejected");
+
+        assert_eq!(Some(PathBuf::from("frames")), options.output);
+        assert_eq!(ImageFormat::Svg, options.image);
+        assert_eq!(2, options.scale);
+
+        let options = parse_args(&args("viz 12 --output frames")).expect("Valid arguments rejected");
+        assert_eq!(ImageFormat::Gif, options.image);
+        assert_eq!(DEFAULT_SCALE, options.scale);
+
+        let err = parse_args(&args("viz 12 --output frames --image png")).err().expect("No error returned.");
+        assert_eq!("Invalid image format: png", err);
+
+        let err = parse_args(&args("viz 12 --image ppm")).err().expect("No error returned.");
+        assert_eq!("--image can only be used with --output.", err);
+
+        let err = parse_args(&args("viz 12 --output frames --headless")).err().expect("No error returned.");
+        assert_eq!("--output and --headless cannot be used together.", err);
+
+        let err = parse_args(&args("3 --output frames")).err().expect("No error returned.");
+        assert_eq!("--output can only be used when visualising a day.", err);
+    }
+
+    #[test]
+    fn gif_delay() {
+        assert_eq!(10, frame_delay(Some(10.0)));
+        assert_eq!(33, frame_delay(Some(3.0)));
+        assert_eq!(2, frame_delay(Some(1000.0)));
+        assert_eq!(2, frame_delay(None));
+    }
+
+    #[test]
+    fn debug() {
+        let options = parse_args(&args("debug 8 --part 2")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Debug, options.mode);
+        assert_eq!(vec![8], options.days);
+        assert_eq!(vec![Part::Two], options.parts);
+
+        let err = parse_args(&args("debug 8 11")).err().expect("No error returned.");
+        assert_eq!("debug requires a single day.", err);
+
+        let err = parse_args(&args("debug 8 --input -")).err().expect("No error returned.");
+        assert_eq!("debug reads commands from standard input, so can't read the puzzle input from it.", err);
+    }
+
+    #[test]
+    fn passwords() {
+        let options = parse_args(&args("passwords")).expect("Valid arguments rejected");
+
+        assert_eq!(Mode::Passwords, options.mode);
+        assert_eq!(vec![2], options.days);
+        assert_eq!(vec!["sled", "toboggan"], options.policies);
+
+        let options = parse_args(&["passwords", "--policy", "sled and not toboggan", "--policy", "sled"].map(String::from))
+            .expect("Valid arguments rejected");
+        assert_eq!(vec!["sled and not toboggan", "sled"], options.policies);
+
+        let err = parse_args(&args("passwords --policy luge")).err().expect("No error returned.");
+        assert_eq!("Invalid policy 'luge': Column 1: Unknown policy: luge (policies are sled, toboggan)", err);
+
+        let err = parse_args(&args("passwords 3")).err().expect("No error returned.");
+        assert_eq!("passwords only checks day 2.", err);
+
+        let err = parse_args(&args("2 --policy sled")).err().expect("No error returned.");
+        assert_eq!("--policy can only be used when checking passwords.", err);
+    }
+
+    #[test]
+    fn headless_only_when_visualising() {
+        let err = parse_args(&args("11 --headless")).err().expect("No error returned.");
+        assert_eq!("--headless can only be used when visualising a day.", err);
+    }
+
+    #[test]
+    fn run_mode() {
+        let options = parse_args(&args("4")).expect("Valid arguments rejected");
+        assert_eq!(Mode::Run, options.mode);
+    }
+
+    #[test]
+    fn input_and_data_dir() {
+        let err = parse_args(&args("9 --input a.txt --data-dir b")).err().expect("No error returned.");
+        assert_eq!("--input and --data-dir cannot be used together.", err);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    // Solve each day and print the answers.
+    Run,
+
+    // Check each day's answers against the answer file.
+    Verify,
+
+    // Time each day's parse step and parts.
+    Bench,
+
+    // Print a random input for a day.
+    Gen,
+
+    // Create a new day's module and files.
+    New,
+
+    // Draw each step of solving a day.
+    Viz,
+
+    // Step through solving a day interactively.
+    Debug,
+
+    // Count the day 2 passwords valid under several policies.
+    Passwords
+}
+
+// Number of times each step is run when benchmarking, unless given.
+const DEFAULT_RUNS: usize = 10;
+
+// Longest each part may take when running days, unless given.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Size of generated inputs, unless given.
+const DEFAULT_SIZE: usize = 100;
+
+// Pixels across each character of a saved frame, unless given.
+const DEFAULT_SCALE: usize = 4;
+
+struct Options {
+    mode: Mode,
+    days: Vec<u32>,
+    parts: Vec<Part>,
+    source: Source,
+    answers: Option<PathBuf>,
+    runs: usize,
+    baseline: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+    format: Format,
+    jobs: usize,
+    timeout: Option<Duration>,
+    seed: u64,
+    size: usize,
+    fps: Option<f64>,
+    headless: bool,
+    output: Option<PathBuf>,
+    image: ImageFormat,
+    scale: usize,
+    policies: Vec<String>
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut mode = Mode::Run;
+    let mut days = Vec::new();
+    let mut parts = Part::both().to_vec();
+    let mut input = None;
+    let mut data_dir = None;
+    let mut answers = None;
+    let mut runs = DEFAULT_RUNS;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut format = None;
+    let mut jobs = None;
+    let mut timeout = None;
+    let mut seed = None;
+    let mut size = None;
+    let mut fps = None;
+    let mut headless = false;
+    let mut output = None;
+    let mut image = None;
+    let mut scale = None;
+    let mut policies = Vec::new();
+
+    let mut args = args.iter().peekable();
+
+    match args.peek().map(|a| a.as_str()) {
+        Some("verify") => mode = Mode::Verify,
+        Some("bench") => mode = Mode::Bench,
+        Some("gen") => mode = Mode::Gen,
+        Some("new") => mode = Mode::New,
+        Some("viz") => mode = Mode::Viz,
+        Some("debug") => mode = Mode::Debug,
+        Some("passwords") => mode = Mode::Passwords,
+        _ => ()
+    };
+
+    if mode != Mode::Run {
+        args.next();
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let n = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--runs requires a value."))
+                };
+
+                runs = match n.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid number of runs: {}", n))
+                };
+            },
+            "--seed" => {
+                let n = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--seed requires a value."))
+                };
+
+                seed = match n.parse() {
+                    Ok(n) => Some(n),
+                    _ => return Err(format!("Invalid seed: {}", n))
+                };
+            },
+            "--size" => {
+                let n = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--size requires a value."))
+                };
+
+                size = match n.parse() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("Invalid size: {}", n))
+                };
+            },
+            "--fps" => {
+                let n = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--fps requires a value."))
+                };
+
+                fps = match n.as_str() {
+                    "none" => Some(None),
+                    _ => match n.parse::<f64>() {
+                        Ok(n) if n > 0.0 && n.is_finite() => Some(Some(n)),
+                        _ => return Err(format!("Invalid frame rate: {}", n))
+                    }
+                };
+            },
+            "--headless" => headless = true,
+            "--policy" => {
+                let policy = match args.next() {
+                    Some(p) => p,
+                    None => return Err(String::from("--policy requires a value."))
+                };
+
+                if let Err(e) = day2::parse_policy(policy) {
+                    return Err(format!("Invalid policy '{}': {}", policy, e));
+                }
+
+                policies.push(policy.clone());
+            },
+            "--output" => {
+                match args.next() {
+                    Some(o) => output = Some(PathBuf::from(o)),
+                    None => return Err(String::from("--output requires a value."))
+                }
+            },
+            "--image" => {
+                let name = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--image requires a value."))
+                };
+
+                image = match ImageFormat::from_name(name) {
+                    Some(f) => Some(f),
+                    None => return Err(format!("Invalid image format: {}", name))
+                };
+            },
+            "--scale" => {
+                let n = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--scale requires a value."))
+                };
+
+                scale = match n.parse() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("Invalid scale: {}", n))
+                };
+            },
+            "--baseline" => {
+                match args.next() {
+                    Some(b) => baseline = Some(PathBuf::from(b)),
+                    None => return Err(String::from("--baseline requires a value."))
+                };
+            },
+            "--save-baseline" => {
+                match args.next() {
+                    Some(b) => save_baseline = Some(PathBuf::from(b)),
+                    None => return Err(String::from("--save-baseline requires a value."))
+                };
+            },
+            "--jobs" => {
+                let n = match args.next() {
+                    Some(n) => n,
+                    None => return Err(String::from("--jobs requires a value."))
+                };
+
+                jobs = match n.parse() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("Invalid number of jobs: {}", n))
+                };
+            },
+            "--timeout" => {
+                let t = match args.next() {
+                    Some(t) => t,
+                    None => return Err(String::from("--timeout requires a value."))
+                };
+
+                timeout = match t.as_str() {
+                    "none" => Some(None),
+                    _ => match t.parse::<f64>() {
+                        Ok(secs) if secs > 0.0 && secs.is_finite() => Some(Some(Duration::from_secs_f64(secs))),
+                        _ => return Err(format!("Invalid timeout: {}", t))
+                    }
+                };
+            },
+            "--format" => {
+                let name = match args.next() {
+                    Some(f) => f,
+                    None => return Err(String::from("--format requires a value."))
+                };
+
+                format = match Format::from_name(name) {
+                    Some(f) => Some(f),
+                    None => return Err(format!("Invalid format: {}", name))
+                };
+            },
+            "--answers" => {
+                match args.next() {
+                    Some(a) => answers = Some(PathBuf::from(a)),
+                    None => return Err(String::from("--answers requires a value."))
+                };
+            },
+            "--input" => {
+                match args.next() {
+                    Some(i) => input = Some(Source::from_arg(i)),
+                    None => return Err(String::from("--input requires a value."))
+                };
+            },
+            "--data-dir" => {
+                match args.next() {
+                    Some(d) => data_dir = Some(Source::DataDir(PathBuf::from(d))),
+                    None => return Err(String::from("--data-dir requires a value."))
+                };
+            },
+            "--part" => {
+                let part = match args.next() {
+                    Some(p) => p,
+                    None => return Err(String::from("--part requires a value."))
+                };
+
+                let part = match part.parse().ok().and_then(Part::from_number) {
+                    Some(p) => p,
+                    None => return Err(format!("Invalid part: {}", part))
+                };
+
+                parts = vec![part];
+            },
+            "all" => {
+                days.extend(days::all().iter().map(|p| p.day()));
+            },
+            _ => {
+                let day: u32 = match arg.parse() {
+                    Ok(d) => d,
+                    Err(_) => return Err(format!("Invalid day: {}", arg))
+                };
+
+                if mode == Mode::New {
+                    if !(1..=25).contains(&day) {
+                        return Err(format!("Invalid day: {}", arg));
+                    }
+                } else if days::get(day).is_none() {
+                    return Err(format!("No solution for day {}.", day));
+                }
+
+                days.push(day);
+            }
+        }
+    }
+
+    if mode == Mode::Gen && days.len() != 1 {
+        return Err(String::from("gen requires a single day."));
+    }
+
+    if mode == Mode::New && days.len() != 1 {
+        return Err(String::from("new requires a single day."));
+    }
+
+    if mode == Mode::Viz && days.len() != 1 {
+        return Err(String::from("viz requires a single day."));
+    }
+
+    if mode == Mode::Debug && days.len() != 1 {
+        return Err(String::from("debug requires a single day."));
+    }
+
+    if mode == Mode::Passwords {
+        if !days.is_empty() {
+            return Err(String::from("passwords only checks day 2."));
+        }
+
+        days.push(2);
+
+        if policies.is_empty() {
+            policies = vec![String::from("sled"), String::from("toboggan")];
+        }
+    } else if !policies.is_empty() {
+        return Err(String::from("--policy can only be used when checking passwords."));
+    }
+
+    if days.is_empty() {
+        if mode != Mode::Run {
+            days.extend(days::all().iter().map(|p| p.day()));
+        } else {
+            return Err(String::from("No days given."));
+        }
+    }
+
+    let source = match (input, data_dir) {
+        (Some(_), Some(_)) => return Err(String::from("--input and --data-dir cannot be used together.")),
+        (Some(_), None) if days.len() > 1 => return Err(String::from("--input can only be used with a single day.")),
+        (Some(source), None) | (None, Some(source)) => source,
+        (None, None) => Source::default()
+    };
+
+    if mode == Mode::Debug && source == Source::Stdin {
+        return Err(String::from("debug reads commands from standard input, so can't read the puzzle input from it."));
+    }
+
+    if mode != Mode::Run {
+        let run_only = [
+            ("--format", format.is_some()),
+            ("--jobs", jobs.is_some()),
+            ("--timeout", timeout.is_some())
+        ];
+
+        if let Some((flag, _)) = run_only.iter().find(|(_, given)| *given) {
+            return Err(format!("{} can only be used when running days.", flag));
+        }
+    }
+
+    if mode != Mode::Gen {
+        let gen_only = [
+            ("--seed", seed.is_some()),
+            ("--size", size.is_some())
+        ];
+
+        if let Some((flag, _)) = gen_only.iter().find(|(_, given)| *given) {
+            return Err(format!("{} can only be used when generating input.", flag));
+        }
+    }
+
+    if mode != Mode::Viz {
+        let viz_only = [
+            ("--fps", fps.is_some()),
+            ("--headless", headless),
+            ("--output", output.is_some()),
+            ("--image", image.is_some()),
+            ("--scale", scale.is_some())
+        ];
+
+        if let Some((flag, _)) = viz_only.iter().find(|(_, given)| *given) {
+            return Err(format!("{} can only be used when visualising a day.", flag));
+        }
+    }
+
+    if output.is_none() {
+        let output_only = [
+            ("--image", image.is_some()),
+            ("--scale", scale.is_some())
+        ];
+
+        if let Some((flag, _)) = output_only.iter().find(|(_, given)| *given) {
+            return Err(format!("{} can only be used with --output.", flag));
+        }
+    } else if headless {
+        return Err(String::from("--output and --headless cannot be used together."));
+    }
+
+    return Ok(Options {
+        mode,
+        days,
+        parts,
+        source,
+        answers,
+        runs,
+        baseline,
+        save_baseline,
+        format: format.unwrap_or(Format::Text),
+        jobs: jobs.unwrap_or_else(parallel::default_jobs),
+        timeout: timeout.unwrap_or(Some(DEFAULT_TIMEOUT)),
+        seed: seed.unwrap_or(0),
+        size: size.unwrap_or(DEFAULT_SIZE),
+        fps: fps.unwrap_or(Some(viz::DEFAULT_FPS)),
+        headless,
+        output,
+        image: image.unwrap_or(ImageFormat::Gif),
+        scale: scale.unwrap_or(DEFAULT_SCALE),
+        policies
+    });
+}
+
+// Solves each day, several at once, and prints a record of each part
+// in the chosen format, in day order.
+// Returns whether every part could be solved.
+fn run(options: &Options) -> bool {
+    let mut ok = true;
+
+    if let Some(header) = options.format.header() {
+        println!("{}", header);
+    }
+
+    parallel::map_ordered(&options.days, options.jobs, |&day| run_day(options, day), |(records, diagnostic)| {
+        // As text, a parse error is shown once, with the line it's on,
+        // rather than as the reason each part failed.
+        if let (Format::Text, Some(diagnostic)) = (options.format, &diagnostic) {
+            eprint!("{}", diagnostic);
+            ok = false;
+            return;
+        }
+
+        for record in &records {
+            let line = options.format.record(record);
+
+            // Failures are only kept apart from the answers as text;
+            // the other formats are read by tools that want every record.
+            if record.status != RecordStatus::Solved && options.format == Format::Text {
+                eprintln!("{}", line);
+            } else {
+                println!("{}", line);
+            }
+        }
+
+        ok &= records.iter().all(|r| r.status == RecordStatus::Solved);
+    });
+
+    return ok;
+}
+
+// Solves the chosen parts of a single day, giving a record of each.
+// If the input can't be read or parsed, every part fails with the same error,
+// and a parse error is also rendered showing where in the input it is.
+fn run_day(options: &Options, day: u32) -> (Vec<Record>, Option<String>) {
+    // We checked that each day exists when parsing arguments.
+    let puzzle = days::get(day).unwrap();
+
+    let failed = |error: String, input: Option<String>| -> Vec<Record> {
+        return options.parts.iter().map(|&part| Record {
+            day,
+            part,
+            status: RecordStatus::Failed,
+            answer: None,
+            error: Some(error.clone()),
+            elapsed: Duration::from_secs(0),
+            input: input.clone()
+        }).collect();
+    };
+
+    let input = match options.source.read(day) {
+        Ok(i) => i,
+        Err(e) => return (failed(format!("could not read {}: {}", options.source.describe(day), e), None), None)
+    };
+
+    let key = answers::key(&input);
+
+    let parsed: Arc<dyn Parsed> = match puzzle.parse(&input) {
+        Ok(p) => Arc::from(p),
+        Err(e @ Error::Parse { .. }) => {
+            let e = e.in_file(options.source.describe(day));
+            return (failed(e.to_string(), Some(key)), Some(e.render(&input)));
+        },
+        Err(e) => return (failed(format!("{}: {}", options.source.describe(day), e), Some(key)), None)
+    };
+
+    let mut records = Vec::new();
+
+    for &part in &options.parts {
+        let parsed = Arc::clone(&parsed);
+
+        let start = Instant::now();
+        let result = parallel::with_timeout(options.timeout, move || parsed.solve(part));
+        let elapsed = start.elapsed();
+
+        let (status, answer, error) = match result {
+            Ok(Ok(answer)) => (RecordStatus::Solved, Some(answer), None),
+            Ok(Err(e)) => (RecordStatus::Failed, None, Some(e.to_string())),
+            Err(Unfinished::Panicked) => (RecordStatus::Failed, None, Some(String::from("Panicked"))),
+            Err(Unfinished::TimedOut) => {
+                // We only time out if there is a limit.
+                let limit = options.timeout.unwrap();
+                let error = format!("Timed out after {}", bench::format_duration(limit));
+
+                (RecordStatus::TimedOut, None, Some(error))
+            }
+        };
+
+        records.push(Record { day, part, status, answer, error, elapsed, input: Some(key.clone()) });
+    }
+
+    return (records, None);
+}
+
+// Checks each day against the answer file and prints a table of the results.
+// Returns whether every answer checked was correct.
+fn verify(options: &Options) -> bool {
+    let path = match &options.answers {
+        Some(p) => p.clone(),
+        None => options.source.answers_path()
+    };
+
+    let answers = match Answers::load(&path) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    let mut outcomes = Vec::new();
+
+    for &day in &options.days {
+        let puzzle = days::get(day).unwrap();
+
+        match verify::verify(puzzle.as_ref(), &options.source, &answers, &options.parts) {
+            Ok(o) => outcomes.extend(o),
+            Err(e) => {
+                eprintln!("Day {}: {}: {}", day, options.source.describe(day), e);
+                ok = false;
+            }
+        };
+    }
+
+    print!("{}", verify::table(&outcomes));
+
+    return ok && outcomes.iter().all(|o| o.status != Status::Fail);
+}
+
+// Times each day and prints a table of the results,
+// saving or comparing against a baseline if asked to.
+// Returns whether every day could be benchmarked.
+fn bench(options: &Options) -> bool {
+    let baseline = match &options.baseline {
+        Some(path) => match Baseline::load(path) {
+            Ok(b) => Some(b),
+            Err(e) => {
+                eprintln!("{}", e);
+                return false;
+            }
+        },
+        None => None
+    };
+
+    let mut ok = true;
+    let mut measurements = Vec::new();
+
+    for &day in &options.days {
+        let puzzle = days::get(day).unwrap();
+
+        let input = match options.source.read(day) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("Day {}: could not read {}: {}", day, options.source.describe(day), e);
+                ok = false;
+                continue;
+            }
+        };
+
+        match bench::bench(puzzle.as_ref(), &input, &options.parts, options.runs) {
+            Ok(m) => measurements.extend(m),
+            Err(e) => {
+                eprintln!("Day {}: {}: {}", day, options.source.describe(day), e);
+                ok = false;
+            }
+        }
+    }
+
+    print!("{}", bench::table(&measurements, baseline.as_ref()));
+
+    if let Some(path) = &options.save_baseline {
+        if let Err(e) = fs::write(path, Baseline::format(&measurements)) {
+            eprintln!("Could not write {}: {}", path.display(), e);
+            ok = false;
+        }
+    }
+
+    return ok;
+}
+
+// Draws each step of solving the given parts of a day, in the
+// terminal, to files in the output directory, or, if headless, nowhere,
+// printing how many frames there were.
+// Returns whether every part could be drawn.
+fn visualise(options: &Options) -> bool {
+    let day = options.days[0];
+    let puzzle = days::get(day).unwrap();
+
+    let input = match options.source.read(day) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Day {}: could not read {}: {}", day, options.source.describe(day), e);
+            return false;
+        }
+    };
+
+    let parsed = match puzzle.parse(&input) {
+        Ok(p) => p,
+        Err(e) => {
+            eprint!("{}", e.in_file(options.source.describe(day)).render(&input));
+            return false;
+        }
+    };
+
+    for &part in &options.parts {
+        let result = if options.headless {
+            let mut headless = Headless::new();
+            let result = parsed.visualise(part, &mut headless);
+
+            if result.is_ok() {
+                println!("Day {}, part {}: {} frames", day, part, headless.frames.len());
+            }
+
+            result
+        } else if let Some(dir) = &options.output {
+            save(parsed.as_ref(), day, part, dir, options)
+        } else {
+            let stdout = io::stdout();
+            let mut terminal = Terminal::new(stdout.lock(), Viewport::terminal()).fps(options.fps);
+
+            parsed.visualise(part, &mut terminal)
+        };
+
+        if let Err(e) = result {
+            eprintln!("Day {}, part {}: {}", day, part, e);
+            return false;
+        }
+    }
+
+    return true;
+}
+
+// Saves the frames of visualising one part of a day in a directory, as
+// <dir>/dayN-partP.gif or as numbered files in <dir>/dayN-partP/,
+// and prints where they were saved.
+fn save(parsed: &dyn Parsed, day: u32, part: Part, dir: &Path, options: &Options) -> Result<(), Error> {
+    let name = format!("day{}-part{}", day, part);
+
+    let (mut renderer, path): (Box<dyn Renderer>, PathBuf) = match options.image {
+        ImageFormat::Gif => {
+            fs::create_dir_all(dir)?;
+
+            let path = dir.join(format!("{}.gif", name));
+            (Box::new(Gif::create(&path, options.scale, frame_delay(options.fps))?), path)
+        },
+        format => {
+            let path = dir.join(&name);
+            (Box::new(Images::new(&path, format, options.scale)?), path)
+        }
+    };
+
+    parsed.visualise(part, renderer.as_mut())?;
+    renderer.finish()?;
+
+    println!("Day {}, part {}: saved to {}", day, part, path.display());
+    return Ok(());
+}
+
+// Time each frame of a GIF is shown for, in hundredths of a second, to
+// play at the given frame rate. Most viewers slow down anything shorter
+// than 2, so that's used when there's no frame rate.
+fn frame_delay(fps: Option<f64>) -> u16 {
+    return match fps {
+        Some(fps) => ((100.0 / fps).round() as u16).max(export::MIN_GIF_DELAY),
+        None => export::MIN_GIF_DELAY
+    };
+}
+
+// Steps through solving the given parts of a day, one after the other,
+// with commands read from standard input.
+// Returns whether every part could be stepped through.
+fn debug(options: &Options) -> bool {
+    let day = options.days[0];
+    let puzzle = days::get(day).unwrap();
+
+    let input = match options.source.read(day) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Day {}: could not read {}: {}", day, options.source.describe(day), e);
+            return false;
+        }
+    };
+
+    let parsed = match puzzle.parse(&input) {
+        Ok(p) => p,
+        Err(e) => {
+            eprint!("{}", e.in_file(options.source.describe(day)).render(&input));
+            return false;
+        }
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    for &part in &options.parts {
+        let mut simulation = match parsed.simulate(part) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Day {}, part {}: {}", day, part, e);
+                return false;
+            }
+        };
+
+        println!("Day {}, part {}: {} (type help for commands)", day, part, simulation.summary());
+
+        match Debugger::new(stdin.lock(), stdout.lock()).run(simulation.as_mut()) {
+            Ok(true) => println!(),
+            Ok(false) => break,
+            Err(e) => {
+                eprintln!("Day {}, part {}: {}", day, part, e);
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+// Counts the day 2 passwords valid under each policy, and prints
+// the counts. Returns whether the passwords could be read.
+fn passwords(options: &Options) -> bool {
+    let input = match options.source.read(2) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Day 2: could not read {}: {}", options.source.describe(2), e);
+            return false;
+        }
+    };
+
+    let passwords = match Day2::parse(&input) {
+        Ok(p) => p,
+        Err(e) => {
+            eprint!("{}", e.in_file(options.source.describe(2)).render(&input));
+            return false;
+        }
+    };
+
+    // Every policy was checked when parsing the arguments.
+    let policies: Vec<Box<dyn day2::Policy>> = options.policies.iter()
+        .map(|p| day2::parse_policy(p).unwrap())
+        .collect();
+
+    let counts = day2::count_valid(&passwords, &policies);
+
+    for (policy, count) in options.policies.iter().zip(counts) {
+        println!("{}: {} of {} passwords valid", policy, count, passwords.len());
+    }
+
+    return true;
+}
+
+// Prints a random input for the given day.
+// Returns whether there is a generator for it.
+fn gen(options: &Options) -> bool {
+    let day = options.days[0];
+
+    return match gen::input(day, options.seed, options.size) {
+        Some(input) => {
+            print!("{}", input);
+            true
+        },
+        None => {
+            eprintln!("Day {}: no input generator", day);
+            false
+        }
+    };
+}
+
+// Sets up a new day in the crate in the current directory,
+// and lists the files created.
+// Returns whether the day could be set up.
+fn new(options: &Options) -> bool {
+    let day = options.days[0];
+
+    return match scaffold::create(Path::new("."), day) {
+        Ok(created) => {
+            for path in created {
+                println!("Created {}", path.display());
+            }
+
+            println!("Registered day {} in src/days/mod.rs", day);
+            true
+        },
+        Err(e) => {
+            eprintln!("Day {}: {}", day, e);
+            false
+        }
+    };
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_args(&args) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let ok = match options.mode {
+        Mode::Run => run(&options),
+        Mode::Verify => verify(&options),
+        Mode::Bench => bench(&options),
+        Mode::Gen => gen(&options),
+        Mode::New => new(&options),
+        Mode::Viz => visualise(&options),
+        Mode::Debug => debug(&options),
+        Mode::Passwords => passwords(&options)
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,846 @@
+// Advent of Code 2020
+// Day 1
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // First example - find product of two numbers in list
+    // which sum to 2020.
+    #[test]
+    fn example() {
+        let n = vec![1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(514579, Day1::part1(&n).expect("No match found"));
+    }
+
+    // Second example - find product of three numbers in list
+    // which sum to 2020.
+    #[test]
+    fn example_three() {
+        let n = vec![1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(241861950, Day1::part2(&n).expect("No match found"));
+    }
+
+    #[test]
+    fn no_match() {
+        let n = vec![1, 2, 3];
+
+        let err = Day1::part1(&n).expect_err("No error returned.");
+        assert_eq!("No solution: No 2 numbers sum to 2020", err.to_string());
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = Day1::parse("1721\n979\nabc\n").expect_err("No error returned.");
+        assert_eq!("Line 3, column 1: Not a number: abc", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_find_match {
+    use super::*;
+
+    #[test]
+    fn values_and_indices() {
+        let n = vec![1721, 979, 366, 299, 675, 1456];
+        let found = find_match(&n, 2, 2020).expect("No match found");
+
+        assert_eq!(vec![0, 3], found.indices);
+        assert_eq!(vec![1721, 299], found.values);
+        assert_eq!(Some(514579), found.product());
+
+        let found = find_match(&n, 3, 2020).expect("No match found");
+        assert_eq!(vec![1, 2, 4], found.indices);
+        assert_eq!(vec![979, 366, 675], found.values);
+    }
+
+    // A number can be used as many times as it appears, but no more.
+    #[test]
+    fn duplicates() {
+        let n = vec![1010, 7, 1010];
+        assert_eq!(Some(vec![0, 2]), find_match(&n, 2, 2020).map(|m| m.indices));
+
+        let n = vec![1010, 7];
+        assert_eq!(None, find_match(&n, 2, 2020));
+
+        let n = vec![5, 5, 5, 2005];
+        assert_eq!(Some(vec![0, 1, 2, 3]), find_match(&n, 4, 2020).map(|m| m.indices));
+    }
+
+    // Of several matches, the one whose indices come first is found.
+    #[test]
+    fn first_match() {
+        let n = vec![1, 3, 2, 2, 1, 3];
+
+        assert_eq!(Some(vec![0, 1]), find_match(&n, 2, 4).map(|m| m.indices));
+        assert_eq!(Some(vec![0, 3]), find_match(&n[1..], 2, 4).map(|m| m.indices));
+    }
+
+    #[test]
+    fn signed() {
+        let n: Vec<i32> = vec![-5, 12, 2025, -3];
+        let found = find_match(&n, 2, 2020).expect("No match found");
+
+        assert_eq!(vec![-5, 2025], found.values);
+        assert_eq!(Some(-10125), found.product());
+
+        assert_eq!(Some(vec![0, 3]), find_match(&n, 2, -8).map(|m| m.indices));
+    }
+
+    #[test]
+    fn wide() {
+        let n: Vec<u64> = vec![4_000_000_000, 10, 6_000_000_000];
+        let found = find_match(&n, 2, 10_000_000_000).expect("No match found");
+
+        assert_eq!(vec![0, 2], found.indices);
+
+        // 24,000,000,000,000,000,000 doesn't fit in a u64.
+        assert_eq!(None, found.product());
+
+        let n: Vec<i64> = vec![i64::MIN, 1, i64::MAX];
+        assert_eq!(Some(vec![0, 2]), find_match(&n, 2, -1).map(|m| m.indices));
+    }
+
+    // Sums which would overflow along the way aren't mistaken for matches.
+    #[test]
+    fn overflow() {
+        let n: Vec<u32> = vec![u32::MAX, 2, 3];
+        assert_eq!(None, find_match(&n, 2, 1));
+
+        let n: Vec<i32> = vec![i32::MAX, i32::MAX, -3];
+        assert_eq!(None, find_match(&n, 3, 1));
+
+        // Nor are matches missed when what's left of the sum part way
+        // through doesn't fit: i32::MIN - 1 is out of range.
+        let n: Vec<i32> = vec![1, i32::MIN, -1];
+        assert_eq!(Some(vec![0, 1, 2]), find_match(&n, 3, i32::MIN).map(|m| m.indices));
+        assert_eq!(1, count_matches(&n, 3, i32::MIN));
+    }
+
+    #[test]
+    fn group_sizes() {
+        let n = vec![3, 4];
+
+        assert_eq!(Some(vec![1]), find_match(&n, 1, 4).map(|m| m.indices));
+        assert_eq!(None, find_match(&n, 3, 7));
+        assert_eq!(None, find_match(&n, 0, 0));
+    }
+}
+
+#[cfg(test)]
+mod test_all_matches {
+    use super::*;
+
+    use crate::gen::Rng;
+    use crate::property;
+
+    #[test]
+    fn example() {
+        let n = vec![1721, 979, 366, 299, 675, 1456];
+
+        let pairs: Vec<Match<u32>> = all_matches(&n, 2, 2020);
+        assert_eq!(vec![Match { indices: vec![0, 3], values: vec![1721, 299] }], pairs);
+
+        assert_eq!(1, count_matches(&n, 3, 2020));
+    }
+
+    // Matches come in order of their indices, however
+    // the numbers are ordered.
+    #[test]
+    fn sorted() {
+        let n = vec![5, 1, 4, 2, 3, 0];
+        let found: Vec<Vec<usize>> = all_matches(&n, 2, 5).into_iter().map(|m| m.indices).collect();
+
+        assert_eq!(vec![vec![0, 5], vec![1, 2], vec![3, 4]], found);
+
+        let found: Vec<Vec<usize>> = all_matches(&n, 3, 6).into_iter().map(|m| m.indices).collect();
+        assert_eq!(vec![vec![0, 1, 5], vec![1, 3, 4], vec![2, 3, 5]], found);
+    }
+
+    // Each number can be used once for each time it appears.
+    #[test]
+    fn duplicates() {
+        let n = vec![1, 1, 1, 1];
+
+        assert_eq!(6, count_matches(&n, 2, 2));
+        assert_eq!(4, count_matches(&n, 3, 3));
+        assert_eq!(vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3]],
+            all_matches(&n, 2, 2).into_iter().map(|m| m.indices).collect::<Vec<_>>());
+
+        let n = vec![2, 3, 2, 3, 3];
+        assert_eq!(6, count_matches(&n, 2, 5));
+    }
+
+    #[test]
+    fn signed() {
+        let n: Vec<i64> = vec![-3, 3, 0, -3, 6, i64::MAX, i64::MIN];
+
+        assert_eq!(vec![vec![0, 1], vec![1, 3]], all_matches(&n, 2, 0).into_iter().map(|m| m.indices).collect::<Vec<_>>());
+        assert_eq!(vec![vec![5, 6]], all_matches(&n, 2, -1).into_iter().map(|m| m.indices).collect::<Vec<_>>());
+        assert_eq!(3, count_matches(&n, 3, 0));
+    }
+
+    #[test]
+    fn nothing() {
+        assert_eq!(0, all_matches(&[1u32, 2], 2, 5).len());
+        assert_eq!(0, count_matches(&[1u32, 2], 3, 3));
+        assert_eq!(0, count_matches(&[0u32], 0, 0));
+    }
+
+    // Pairs among tens of thousands of numbers are found without
+    // trying every pair.
+    #[test]
+    fn large() {
+        let mut rng = Rng::new(22);
+        let n: Vec<u32> = (0..50_000).map(|_| rng.below(1_000_000) as u32).collect();
+
+        let count = count_matches(&n, 2, 1_000_000);
+
+        assert!(count > 0);
+        assert_eq!(count, all_matches(&n, 2, 1_000_000).len() as u64);
+    }
+
+    // Every combination of indices, to check against.
+    fn matches_reference(n: &[u64], group_size: usize, sum: u64) -> Vec<Vec<usize>> {
+        let mut found = Vec::new();
+
+        for subset in 0..1u32 << n.len() {
+            if subset.count_ones() as usize != group_size {
+                continue;
+            }
+
+            let indices: Vec<usize> = (0..n.len()).filter(|&i| subset & (1 << i) != 0).collect();
+
+            if indices.iter().map(|&i| n[i]).sum::<u64>() == sum {
+                found.push(indices);
+            }
+        }
+
+        found.sort();
+        return found;
+    }
+
+    #[test]
+    fn matches_reference_implementation() {
+        let generate = |rng: &mut Rng| {
+            let n: Vec<u64> = (0..rng.range(0, 12)).map(|_| rng.below(8)).collect();
+            return (n, (rng.range(1, 4), rng.below(20)));
+        };
+
+        property::assert_holds(500, 22, generate, |(n, (group_size, sum))| {
+            let expected = matches_reference(n, *group_size as usize, *sum);
+            let actual: Vec<Vec<usize>> = all_matches(n, *group_size as usize, *sum).into_iter().map(|m| m.indices).collect();
+            let count = count_matches(n, *group_size as usize, *sum);
+
+            if actual != expected {
+                return Err(format!("Expected {:?}, got {:?}", expected, actual));
+            }
+
+            if count != expected.len() as u64 {
+                return Err(format!("Expected a count of {}, got {}", expected.len(), count));
+            }
+
+            return Ok(());
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_find_subset {
+    use super::*;
+
+    use crate::gen::Rng;
+    use crate::property;
+
+    fn indices<T>(found: &Option<Match<T>>) -> Option<Vec<usize>> {
+        return found.as_ref().map(|m| m.indices.clone());
+    }
+
+    #[test]
+    fn exact() {
+        let n = vec![1721, 979, 366, 299, 675, 1456];
+
+        let found = find_subset(&n, 2020).expect("Error finding subset");
+
+        match found {
+            Subset::Exact(m) => assert_eq!(2020, m.total()),
+            Subset::Closest { .. } => panic!("No exact match found")
+        }
+    }
+
+    // With several subsets to choose from, the one found is
+    // the one whose last number comes first.
+    #[test]
+    fn any_size() {
+        let n = vec![8, 3, 5, 1, 4];
+
+        assert_eq!(Subset::Exact(Match { indices: vec![0, 1, 2, 3], values: vec![8, 3, 5, 1] }), find_subset(&n, 17).unwrap());
+        assert_eq!(Subset::Exact(Match { indices: vec![0, 3], values: vec![8, 1] }), find_subset(&n, 9).unwrap());
+        assert_eq!(Subset::Exact(Match { indices: vec![1, 3], values: vec![3, 1] }), find_subset(&n, 4).unwrap());
+    }
+
+    #[test]
+    fn closest() {
+        let n = vec![10, 20, 50];
+
+        match find_subset(&n, 45).unwrap() {
+            Subset::Closest { under, over } => {
+                assert_eq!(Some(vec![0, 1]), indices(&under));
+                assert_eq!(Some(vec![2]), indices(&over));
+            },
+            exact => panic!("Unexpected match: {:?}", exact)
+        }
+
+        match find_subset(&n, 100).unwrap() {
+            Subset::Closest { under, over } => {
+                assert_eq!(Some(vec![0, 1, 2]), indices(&under));
+                assert_eq!(None, over);
+            },
+            exact => panic!("Unexpected match: {:?}", exact)
+        }
+    }
+
+    // The empty set isn't a match, even for a target of zero.
+    #[test]
+    fn not_empty() {
+        match find_subset(&[5u32, 7], 0).unwrap() {
+            Subset::Closest { under, over } => {
+                assert_eq!(None, under);
+                assert_eq!(Some(vec![0]), indices(&over));
+            },
+            exact => panic!("Unexpected match: {:?}", exact)
+        }
+
+        assert_eq!(true, find_subset(&[-5i32, 7, 5], 0).unwrap().is_exact());
+    }
+
+    #[test]
+    fn signed() {
+        let n: Vec<i64> = vec![-7, 12, -3, 4];
+
+        assert_eq!(Subset::Exact(Match { indices: vec![0, 2], values: vec![-7, -3] }), find_subset(&n, -10).unwrap());
+        assert_eq!(Subset::Exact(Match { indices: vec![0, 1, 2], values: vec![-7, 12, -3] }), find_subset(&n, 2).unwrap());
+    }
+
+    #[test]
+    fn too_big() {
+        let err = find_subset(&[1u64 << 40, 1], 5).expect_err("No error returned.");
+        assert_eq!("Invalid puzzle state: Sums range over 1099511627778 values, more than the limit of 4194304", err.to_string());
+    }
+
+    // The best sums under, at and over the target, from every subset.
+    fn best_reference(n: &[u64], sum: u64) -> (Option<u64>, bool, Option<u64>) {
+        let (mut under, mut exact, mut over) = (None, false, None);
+
+        for subset in 1..1u32 << n.len() {
+            let total: u64 = (0..n.len()).filter(|&i| subset & (1 << i) != 0).map(|i| n[i]).sum();
+
+            if total == sum {
+                exact = true;
+            } else if total < sum {
+                under = under.max(Some(total));
+            } else {
+                over = Some(over.map_or(total, |o: u64| o.min(total)));
+            }
+        }
+
+        return (under, exact, over);
+    }
+
+    #[test]
+    fn matches_reference_implementation() {
+        let generate = |rng: &mut Rng| ((0..rng.range(0, 10)).map(|_| rng.below(30)).collect::<Vec<u64>>(), rng.below(150));
+
+        property::assert_holds(500, 23, generate, |(n, sum)| {
+            let (under, exact, over) = best_reference(n, *sum);
+
+            let found = find_subset(n, *sum).map_err(|e| e.to_string())?;
+            let total = |m: &Option<Match<u64>>| m.as_ref().map(|m| m.total() as u64);
+
+            let actual = match &found {
+                Subset::Exact(m) if m.total() == *sum as i128 => (under, true, over),
+                Subset::Exact(m) => return Err(format!("Exact match {:?} doesn't sum to {}", m, sum)),
+                Subset::Closest { under, over } => (total(under), false, total(over))
+            };
+
+            if exact {
+                return if actual.1 { Ok(()) } else { Err(format!("Missed an exact match, found {:?}", found)) };
+            }
+
+            return if actual == (under, exact, over) {
+                Ok(())
+            } else {
+                Err(format!("Expected {:?}, found {:?}", (under, exact, over), found))
+            };
+        });
+    }
+}
+
+// Integers which matches can be found among. Every one of them
+// fits in an i128, so sums of them can be worked out without
+// overflowing.
+pub trait Integer: Copy + Eq + Hash + fmt::Debug + fmt::Display {
+    fn one() -> Self;
+    fn wide(self) -> i128;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn one() -> $t {
+                    return 1;
+                }
+
+                fn wide(self) -> i128 {
+                    return self as i128;
+                }
+
+                fn checked_mul(self, other: $t) -> Option<$t> {
+                    return <$t>::checked_mul(self, other);
+                }
+            }
+        )*
+    };
+}
+
+integer!(i32, i64, isize, u32, u64, usize);
+
+// Some numbers from a list which sum to a target.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Match<T> {
+    // Where each number is in the list, in increasing order.
+    pub indices: Vec<usize>,
+
+    // The numbers, in the same order.
+    pub values: Vec<T>
+}
+
+impl<T: Integer> Match<T> {
+    // The numbers multiplied together, or None if that overflows.
+    pub fn product(&self) -> Option<T> {
+        return self.values.iter().try_fold(T::one(), |product, &v| product.checked_mul(v));
+    }
+
+    // The numbers added up. This is wider than the numbers themselves,
+    // since a sum of many of them may not fit.
+    pub fn total(&self) -> i128 {
+        return self.values.iter().map(|v| v.wide()).sum();
+    }
+}
+
+// Given an array of numbers <n>, finds <group_size> of them, at different
+// positions, which sum to <sum>. A number may appear more than once, and
+// can be used once for each time it does.
+//
+// If there is more than one match, the one found is the first when
+// comparing their indices in order, so the same input always gives
+// the same match.
+pub fn find_match<T: Integer>(n: &[T], group_size: usize, sum: T) -> Option<Match<T>> {
+    if group_size == 0 {
+        return None;
+    }
+
+    // Where each number appears, in order.
+    let mut positions: HashMap<i128, Vec<usize>> = HashMap::new();
+
+    for (i, v) in n.iter().enumerate() {
+        positions.entry(v.wide()).or_default().push(i);
+    }
+
+    let mut indices = Vec::with_capacity(group_size);
+
+    if !find_match_inner(n, &positions, group_size, sum.wide(), &mut indices) {
+        return None;
+    }
+
+    let values = indices.iter().map(|&i| n[i]).collect();
+    return Some(Match { indices, values });
+}
+
+// Finds <group_size> numbers which sum to <sum>, after those already
+// chosen in <indices>, adding their indices. Returns whether it did.
+//
+// What's left of the sum is kept wide, since it can leave T's range
+// part way through even when the match itself fits.
+fn find_match_inner<T: Integer>(n: &[T], positions: &HashMap<i128, Vec<usize>>, group_size: usize, sum: i128, indices: &mut Vec<usize>) -> bool {
+    let start = indices.last().map_or(0, |&i| i + 1);
+
+    // Base case - the last number is the one that makes up the sum,
+    // so look it up rather than trying each number.
+    if group_size == 1 {
+        let next = positions.get(&sum)
+            .and_then(|p| p.iter().find(|&&i| i >= start));
+
+        return match next {
+            Some(&i) => {
+                indices.push(i);
+                true
+            },
+            None => false
+        };
+    }
+
+    // Recursive case - for each number, we need to find
+    // (<group_size>-1) numbers after it that sum to the rest.
+    for i in start..n.len() {
+        let rest = sum - n[i].wide();
+        indices.push(i);
+
+        if find_match_inner(n, positions, group_size - 1, rest, indices) {
+            return true;
+        }
+
+        indices.pop();
+    }
+
+    return false;
+}
+
+// Every set of <group_size> numbers from <n>, at different positions,
+// which sum to <sum>, in order of their indices.
+//
+// The numbers are sorted, so that the last two of each group can be
+// found by closing in from both ends at once, rather than by trying
+// every pair. That finds them out of order, so they are all found and
+// sorted before any are returned; use count_matches() to only count
+// them.
+pub fn all_matches<T: Integer>(n: &[T], group_size: usize, sum: T) -> Vec<Match<T>> {
+    let sorted = sorted_by_value(n);
+    let mut found = Vec::new();
+
+    each_match(&sorted, 0, group_size, sum.wide(), &mut Vec::new(), &mut |indices| {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        found.push(indices);
+    });
+
+    found.sort_unstable();
+
+    return found.into_iter().map(|indices| {
+        let values = indices.iter().map(|&i| n[i]).collect();
+        return Match { indices, values };
+    }).collect();
+}
+
+// The number of sets all_matches() would find, without listing them.
+pub fn count_matches<T: Integer>(n: &[T], group_size: usize, sum: T) -> u64 {
+    return count_from(&sorted_by_value(n), 0, group_size, sum.wide());
+}
+
+// Each number with its index, in order of value, then of index.
+fn sorted_by_value<T: Integer>(n: &[T]) -> Vec<(i128, usize)> {
+    let mut sorted: Vec<(i128, usize)> = n.iter().enumerate().map(|(i, v)| (v.wide(), i)).collect();
+    sorted.sort_unstable();
+
+    return sorted;
+}
+
+// Whether <group_size> numbers from sorted[start..] could possibly
+// sum to <sum>, going by the smallest and largest there are.
+fn in_reach(sorted: &[(i128, usize)], start: usize, group_size: usize, sum: i128) -> bool {
+    let rest = &sorted[start..];
+
+    if rest.len() < group_size {
+        return false;
+    }
+
+    let smallest: i128 = rest[..group_size].iter().map(|p| p.0).sum();
+    let largest: i128 = rest[rest.len() - group_size..].iter().map(|p| p.0).sum();
+
+    return smallest <= sum && sum <= largest;
+}
+
+// The end of the run of equal values starting at <i>.
+fn run_end(sorted: &[(i128, usize)], i: usize) -> usize {
+    let mut end = i;
+
+    while end < sorted.len() && sorted[end].0 == sorted[i].0 {
+        end += 1;
+    }
+
+    return end;
+}
+
+// Calls <found> with the indices of each set of <group_size> numbers
+// from sorted[start..] which sum to <sum>, added to those in <chosen>.
+fn each_match<F: FnMut(&[usize])>(sorted: &[(i128, usize)], start: usize, group_size: usize, sum: i128, chosen: &mut Vec<usize>, found: &mut F) {
+    if group_size == 0 || !in_reach(sorted, start, group_size, sum) {
+        return;
+    }
+
+    if group_size == 1 {
+        for &(v, i) in &sorted[start..] {
+            if v == sum {
+                chosen.push(i);
+                found(chosen);
+                chosen.pop();
+            }
+        }
+
+        return;
+    }
+
+    if group_size == 2 {
+        let (mut low, mut high) = (start, sorted.len() - 1);
+
+        while low < high {
+            let total = sorted[low].0 + sorted[high].0;
+
+            if total < sum {
+                low += 1;
+            } else if total > sum {
+                high -= 1;
+            } else if sorted[low].0 == sorted[high].0 {
+                // Every pair from low to high sums to <sum>.
+                for a in low..=high {
+                    for b in a + 1..=high {
+                        chosen.extend(&[sorted[a].1, sorted[b].1]);
+                        found(chosen);
+                        chosen.truncate(chosen.len() - 2);
+                    }
+                }
+
+                break;
+            } else {
+                // Pair up the runs of equal values at both ends.
+                let low_end = run_end(sorted, low);
+                let mut high_start = high;
+
+                while sorted[high_start - 1].0 == sorted[high].0 {
+                    high_start -= 1;
+                }
+
+                for a in low..low_end {
+                    for b in high_start..=high {
+                        chosen.extend(&[sorted[a].1, sorted[b].1]);
+                        found(chosen);
+                        chosen.truncate(chosen.len() - 2);
+                    }
+                }
+
+                low = low_end;
+                high = high_start - 1;
+            }
+        }
+
+        return;
+    }
+
+    for first in start..sorted.len() {
+        chosen.push(sorted[first].1);
+        each_match(sorted, first + 1, group_size - 1, sum - sorted[first].0, chosen, found);
+        chosen.pop();
+    }
+}
+
+// Counts the sets each_match() would find, counting the pairs from
+// runs of equal values rather than listing them.
+fn count_from(sorted: &[(i128, usize)], start: usize, group_size: usize, sum: i128) -> u64 {
+    if group_size == 0 || !in_reach(sorted, start, group_size, sum) {
+        return 0;
+    }
+
+    if group_size == 1 {
+        return sorted[start..].iter().filter(|p| p.0 == sum).count() as u64;
+    }
+
+    if group_size == 2 {
+        let (mut low, mut high) = (start, sorted.len() - 1);
+        let mut count = 0;
+
+        while low < high {
+            let total = sorted[low].0 + sorted[high].0;
+
+            if total < sum {
+                low += 1;
+            } else if total > sum {
+                high -= 1;
+            } else if sorted[low].0 == sorted[high].0 {
+                let run = (high - low + 1) as u64;
+                count += run * (run - 1) / 2;
+                break;
+            } else {
+                let low_end = run_end(sorted, low);
+                let mut high_start = high;
+
+                while sorted[high_start - 1].0 == sorted[high].0 {
+                    high_start -= 1;
+                }
+
+                count += ((low_end - low) * (high - high_start + 1)) as u64;
+
+                low = low_end;
+                high = high_start - 1;
+            }
+        }
+
+        return count;
+    }
+
+    return (start..sorted.len())
+        .map(|first| count_from(sorted, first + 1, group_size - 1, sum - sorted[first].0))
+        .sum();
+}
+
+// What find_subset() found.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Subset<T> {
+    // Numbers which sum to the target.
+    Exact(Match<T>),
+
+    // No numbers sum to the target, so those whose sum is nearest below
+    // it and nearest above it, if any sums are.
+    Closest {
+        under: Option<Match<T>>,
+        over: Option<Match<T>>
+    }
+}
+
+impl<T> Subset<T> {
+    pub fn is_exact(&self) -> bool {
+        return matches!(self, Subset::Exact(_));
+    }
+}
+
+// Most different sums find_subset() keeps track of. It keeps 4 bytes
+// for each, so at most 16 MB, and does work for each number times
+// each sum.
+const MAX_SUMS: i128 = 1 << 22;
+
+// Given an array of numbers <n>, finds any number of them, at least one,
+// which sum to <sum>, or failing that the numbers whose sum is closest
+// below and above it.
+//
+// Every sum which can be made is worked out, a number at a time, so the
+// numbers must be small enough that there aren't too many possible sums:
+// from the sum of the negative numbers to the sum of the positive ones.
+// Of several sets with the same sum, the one found is that whose last
+// number comes first.
+pub fn find_subset<T: Integer>(n: &[T], sum: T) -> Result<Subset<T>> {
+    let lowest: i128 = n.iter().map(|v| v.wide().min(0)).sum();
+    let highest: i128 = n.iter().map(|v| v.wide().max(0)).sum();
+    let range = highest - lowest + 1;
+
+    if range > MAX_SUMS {
+        return Err(Error::InvalidState(format!("Sums range over {} values, more than the limit of {}", range, MAX_SUMS)));
+    }
+
+    if n.len() >= u32::MAX as usize {
+        return Err(Error::InvalidState(format!("Too many numbers to find a subset of: {}", n.len())));
+    }
+
+    // For each sum, counting from <lowest>: 0 if it can't be made,
+    // otherwise one more than the index of the number which first
+    // made it, so that the rest of the numbers can be found by taking
+    // that one away.
+    let mut via = vec![0u32; range as usize];
+
+    for (i, v) in n.iter().enumerate() {
+        let v = v.wide();
+
+        // Go through the sums away from the direction adding this number
+        // moves them, so no sum made with it is used to make another.
+        let sums: Box<dyn Iterator<Item = i128>> = if v > 0 {
+            Box::new((lowest..=highest - v).rev())
+        } else {
+            Box::new(lowest - v..=highest)
+        };
+
+        for s in sums {
+            let (from, to) = ((s - lowest) as usize, (s + v - lowest) as usize);
+
+            if via[from] != 0 && via[to] == 0 {
+                via[to] = i as u32 + 1;
+            }
+        }
+
+        let alone = (v - lowest) as usize;
+
+        if via[alone] == 0 {
+            via[alone] = i as u32 + 1;
+        }
+    }
+
+    // The numbers which first made a sum. Each one was added to a sum
+    // made from numbers before it, so work back through them until
+    // there are none.
+    let made = |s: i128| {
+        let mut indices = Vec::new();
+        let (mut s, mut before) = (s, n.len());
+
+        loop {
+            let i = match via[(s - lowest) as usize] as usize {
+                v if v != 0 && v - 1 < before => v - 1,
+                _ => break
+            };
+
+            indices.push(i);
+            s -= n[i].wide();
+            before = i;
+        }
+
+        indices.reverse();
+
+        let values = indices.iter().map(|&i| n[i]).collect();
+        return Match { indices, values };
+    };
+
+    let sum = sum.wide();
+    let reached = |s: i128| lowest <= s && s <= highest && via[(s - lowest) as usize] != 0;
+
+    if reached(sum) {
+        return Ok(Subset::Exact(made(sum)));
+    }
+
+    let under = (lowest..sum.min(highest + 1)).rev().find(|&s| reached(s)).map(made);
+    let over = (sum.max(lowest - 1) + 1..=highest).find(|&s| reached(s)).map(made);
+
+    return Ok(Subset::Closest { under, over });
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>> {
+        return parse::numbers(input);
+    }
+
+    fn part1(input: &Vec<u32>) -> Result<u32> {
+        return product_of_match(input, 2, 2020);
+    }
+
+    fn part2(input: &Vec<u32>) -> Result<u32> {
+        return product_of_match(input, 3, 2020);
+    }
+}
+
+// The product of <group_size> numbers which sum to <sum>.
+fn product_of_match(n: &[u32], group_size: usize, sum: u32) -> Result<u32> {
+    let found = match find_match(n, group_size, sum) {
+        Some(m) => m,
+        None => return Err(Error::NoSolution(format!("No {} numbers sum to {}", group_size, sum)))
+    };
+
+    return match found.product() {
+        Some(product) => Ok(product),
+        None => Err(Error::InvalidState(format!("Product of {:?} overflows", found.values)))
+    };
+}
@@ -0,0 +1,278 @@
+// Advent of Code 2020
+// Day 10
+
+use std::collections::HashMap;
+use std::iter::{FromIterator};
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    use crate::examples;
+
+    fn adapters(name: &str) -> Vec<u32> {
+        let input = examples::read(10, name).expect("Example not found");
+        return Day10::parse(&input).expect("Valid input rejected");
+    }
+
+    #[test]
+    fn test_part1_example1() {
+        let mut adapters = adapters("example1.txt");
+
+        let distribution = get_distribution(&mut adapters).expect("Adapters do not chain");
+        assert_eq!(7, *distribution.get(&1).unwrap());
+        assert_eq!(0, *distribution.get(&2).unwrap());
+        assert_eq!(5, *distribution.get(&3).unwrap());
+    }
+
+    #[test]
+    fn test_part2_example1() {
+        let mut adapters = adapters("example1.txt");
+
+        let arrangements = get_arrangements(&mut adapters);
+        assert_eq!(8, arrangements);
+    }
+
+    #[test]
+    fn test_part1_example2() {
+        let mut adapters = adapters("example2.txt");
+
+        let distribution = get_distribution(&mut adapters).expect("Adapters do not chain");
+        assert_eq!(22, *distribution.get(&1).unwrap());
+        assert_eq!(0, *distribution.get(&2).unwrap());
+        assert_eq!(10, *distribution.get(&3).unwrap());
+    }
+
+    #[test]
+    fn test_part2_example2() {
+        let mut adapters = adapters("example2.txt");
+
+        let arrangements = get_arrangements(&mut adapters);
+        assert_eq!(19208, arrangements);
+    }
+}
+
+#[cfg(test)]
+mod test_errors {
+    use super::*;
+
+    #[test]
+    fn gap_too_large() {
+        let mut adapters = vec![1, 2, 6];
+
+        let err = get_distribution(&mut adapters).expect_err("No error returned.");
+        assert_eq!("No solution: Could not find a valid adapter for value: 2", err.to_string());
+    }
+
+    #[test]
+    fn not_a_number() {
+        let err = Day10::parse("16\n10\nfifteen").expect_err("No error returned.");
+        assert_eq!("Line 3, column 1: Not a number: fifteen", err.to_string());
+    }
+
+    // Input files end with a newline, which isn't an adapter.
+    #[test]
+    fn trailing_newline() {
+        let adapters = Day10::parse("16\n10\n15\n").expect("Valid input rejected");
+        assert_eq!(vec![16, 10, 15], adapters);
+    }
+}
+
+#[cfg(test)]
+mod test_properties {
+    use super::*;
+
+    use crate::gen::Rng;
+    use crate::property;
+
+    // Counts arrangements by trying every subset of the adapters.
+    // The highest adapter is always needed to reach the device.
+    fn arrangements_reference(adapters: &[u32]) -> u64 {
+        let mut adapters = adapters.to_vec();
+        adapters.sort();
+
+        let (&last, rest) = adapters.split_last().unwrap();
+        let mut count = 0;
+
+        for subset in 0..1u32 << rest.len() {
+            let mut current = 0;
+            let mut valid = true;
+
+            let chosen = rest.iter().enumerate().filter(|&(i, _)| subset & (1 << i) != 0);
+
+            for (_, &a) in chosen.chain(std::iter::once((rest.len(), &last))) {
+                if a - current > 3 {
+                    valid = false;
+                    break;
+                }
+
+                current = a;
+            }
+
+            if valid {
+                count += 1;
+            }
+        }
+
+        return count;
+    }
+
+    // Adapters from a list of differences, each taken as 1 to 3
+    // so that any shrunk list is still a valid chain.
+    fn chain(differences: &[u32]) -> Vec<u32> {
+        return differences.iter()
+            .scan(0, |joltage, d| {
+                *joltage += 1 + d % 3;
+                return Some(*joltage);
+            })
+            .collect();
+    }
+
+    #[test]
+    fn arrangements_match_reference() {
+        let generate = |rng: &mut Rng| (0..rng.range(1, 14)).map(|_| rng.below(3) as u32).collect::<Vec<u32>>();
+
+        property::assert_holds(500, 10, generate, |differences| {
+            let adapters = chain(differences);
+
+            if adapters.is_empty() {
+                return Ok(());
+            }
+
+            let expected = arrangements_reference(&adapters);
+            let actual = get_arrangements(&mut adapters.clone());
+
+            return if expected == actual {
+                Ok(())
+            } else {
+                Err(format!("Adapters {:?}: expected {} arrangements, got {}", adapters, expected, actual))
+            };
+        });
+    }
+}
+
+fn get_arrangements(adapters: &mut [u32]) -> u64 {
+    let mut adapters = Vec::from_iter(adapters.iter().copied());
+    adapters.push(0);
+    adapters.sort();
+
+    let mut known: HashMap<(u32, usize), u64> = HashMap::new();
+
+    return get_arrangements_inner(&mut known, &mut adapters, 0);
+}
+
+fn get_arrangements_inner(known: &mut HashMap<(u32, usize), u64>, adapters: &mut Vec<u32>, start: usize) -> u64 {
+    // Length of the list that we have left, and value of the first
+    // item in that sub-list. These two values can be used to uniquely
+    // identify that particular sublist, so that we can cache the number of
+    // permutations for later use.
+    let length = adapters.len() - start;
+    let start_val = adapters[start];
+
+    // Return the cached value if we have it.
+    if known.contains_key(&(start_val, length)) {
+        return *known.get(&(start_val, length)).unwrap();
+    }
+
+    let mut arrangements = 0;
+
+    // For each element i in the list (including both the outlet and device),
+    // for which pos(i) + 2 is within the bounds of the list,
+    // if the element at pos(i) + 2 is within 3 jolts of i,
+    // the element at pos(i) + 1 can be removed, and the chain
+    // still works.
+    //
+    // Therefore we iterate over the list.
+    // Each time we encounter such an element,
+    // we take a branch - one where we remove it
+    // and one where we don't, and count the ways
+    // that sub-list can be arranged.
+    for pos in start..adapters.len()-2 {
+        let i = adapters[pos];
+
+        if adapters[pos+2] - i <= 3 {
+            // Remove the middle item from the vector.
+            let removed = adapters.remove(pos+1);
+
+            // How many ways is it possible to arrange the new list?
+            arrangements += get_arrangements_inner(known, adapters, pos);
+
+            adapters.insert(pos+1, removed);
+        }
+    }
+
+    // Insert into the map of known items.
+    known.insert((start_val, length), arrangements + 1);
+
+    return arrangements + 1;
+}
+
+fn get_distribution(adapters: &mut [u32]) -> Result<HashMap<u32, u32>> {
+    // Sort list of adapters.
+    adapters.sort();
+
+    // Distribution of differences between adapter jolts.
+    let mut distribution: HashMap<u32, u32> = HashMap::new();
+    distribution.insert(1, 0);
+    distribution.insert(2, 0);
+    
+    // Our device is always +3 from the highest adapter value.
+    // So there is always at least one occurance of a difference of 3.
+    distribution.insert(3, 1);
+
+    let mut current = 0;
+
+    // Sort the list of adapters and iterate over them.
+    for &a in adapters.iter() {
+        let difference = a - current;
+
+        if !(1..=3).contains(&difference) {
+            return Err(Error::NoSolution(format!("Could not find a valid adapter for value: {}", current)));
+        }
+
+        *(distribution.get_mut(&difference).unwrap()) += 1;
+
+        current = a;
+    }
+
+    return Ok(distribution);
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u32>> {
+        return parse::numbers(input);
+    }
+
+    // Return the number of 1-jolt differences multiplied
+    // by the number of 3-jolt differences.
+    fn part1(input: &Vec<u32>) -> Result<u32> {
+        let mut input = input.clone();
+
+        let distribution = get_distribution(&mut input)?;
+
+        let num_1 = *distribution.get(&1).unwrap();
+        let num_3 = *distribution.get(&3).unwrap();
+
+        return Ok(num_1 * num_3);
+    }
+
+    // Return the number of distinct ways the adapters
+    // can be arranged.
+    fn part2(input: &Vec<u32>) -> Result<u64> {
+        let mut input = input.clone();
+
+        return Ok(get_arrangements(&mut input));
+    }
+}
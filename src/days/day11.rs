@@ -0,0 +1,370 @@
+// Advent of Code 2020
+// Day 11
+
+use std::fmt;
+use std::mem;
+
+use crate::debug::Simulation;
+use crate::error::Result;
+use crate::grid::{Grid, NEIGHBOURS8};
+use crate::solution::{Part, Solution};
+use crate::viz::{Frame, Renderer};
+
+type State = Grid<Seat>;
+type Rule = fn(&(usize, usize), Seat, &State) -> Seat;
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    #[test]
+    fn test_example_part1() {
+        let input = "
+        L.LL.LL.LL
+        LLLLLLL.LL
+        L.L.L..L..
+        LLLL.LL.LL
+        L.LL.LL.LL
+        L.LLLLL.LL
+        ..L.L.....
+        LLLLLLLLLL
+        L.LLLLLL.L
+        L.LLLLL.LL";
+
+        let input = parse_input(input).expect("Invalid seat layout");
+
+        let seats = stable_occupied_seats(input, rule_part1, None).unwrap();
+        assert_eq!(37, seats);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        let input = "
+        L.LL.LL.LL
+        LLLLLLL.LL
+        L.L.L..L..
+        LLLL.LL.LL
+        L.LL.LL.LL
+        L.LLLLL.LL
+        ..L.L.....
+        LLLLLLLLLL
+        L.LLLLLL.L
+        L.LLLLL.LL";
+
+        let input = parse_input(input).expect("Invalid seat layout");
+
+        let seats = stable_occupied_seats(input, rule_part2, None).unwrap();
+        assert_eq!(26, seats);
+    }
+}
+
+#[cfg(test)]
+mod test_visualise {
+    use super::*;
+
+    use crate::viz::Headless;
+
+    // A frame for each generation, ending with the stable one.
+    #[test]
+    fn generations() {
+        let input = parse_input("L.L\nLLL\nL.L").unwrap();
+
+        let mut headless = Headless::new();
+        Day11::visualise(&input, Part::One, &mut headless).expect("Error visualising");
+
+        let last = headless.frames.last().unwrap();
+
+        assert_eq!("Generation 0: 0 occupied", headless.frames[0].caption());
+        assert_eq!("L.L\nLLL\nL.L\n", headless.frames[0].to_string());
+        assert_eq!("#.#\n#L#\n#.#\n", last.to_string());
+        assert_eq!(format!("Generation {}: 6 occupied", headless.frames.len() - 1), last.caption());
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn invalid_character() {
+        let input = "
+        L.LL
+        LLXL";
+
+        let err = parse_input(input).err().expect("No error returned.");
+        assert_eq!("Line 3, column 11: Invalid grid: unsupported character", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_simulate {
+    use super::*;
+
+    const EXAMPLE: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    #[test]
+    fn generations() {
+        let layout = Day11::parse(EXAMPLE).unwrap();
+        let mut generations = Day11::simulate(&layout, Part::One).unwrap();
+
+        assert_eq!("generation 0, 0 occupied", generations.summary());
+
+        generations.step();
+        assert_eq!("generation 1, 71 occupied", generations.summary());
+
+        while !generations.finished() {
+            generations.step();
+        }
+
+        // Finished as soon as the seats stop changing.
+        assert_eq!(5, generations.steps());
+        assert_eq!(vec![("generation", 5), ("occupied", 37)], generations.variables());
+    }
+
+    #[test]
+    fn state() {
+        let layout = Day11::parse("L.\nLL").unwrap();
+        let mut generations = Day11::simulate(&layout, Part::Two).unwrap();
+
+        generations.step();
+
+        assert_eq!("Generation 1, 3 occupied\n#.\n##\n", generations.state());
+        assert_eq!(true, generations.finished());
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Seat {
+    None,
+    Unoccupied,
+    Occupied
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Seat::Occupied => write!(f, "#"),
+            Seat::Unoccupied => write!(f, "L"),
+            Seat::None => write!(f, ".")
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<State> {
+    return Grid::parse(input, |c| {
+        match c {
+            'L' => Some(Seat::Unoccupied),
+            '#' => Some(Seat::Occupied),
+            '.' => Some(Seat::None),
+            _ => None
+        }
+    });
+}
+
+// Applies a rule until the seats stop changing, and counts how many
+// are occupied. Each generation is drawn if there is a renderer.
+fn stable_occupied_seats(input: State, rule: Rule, mut renderer: Option<&mut dyn Renderer>) -> Result<usize> {
+    let mut current_state = input;
+    let mut generation = 0;
+
+    loop {
+        let occupied = occupied_seats(&current_state);
+
+        if let Some(renderer) = renderer.as_deref_mut() {
+            let caption = format!("Generation {}: {} occupied", generation, occupied);
+            renderer.render(&Frame::from_grid(&current_state).with_caption(caption))?;
+        }
+
+        let new_state = next_generation(&current_state, rule);
+
+        if new_state == current_state {
+            return Ok(occupied);
+        }
+
+        current_state = new_state;
+        generation += 1;
+    }
+}
+
+fn occupied_seats(state: &State) -> usize {
+    return state.iter().filter(|&(_, v)| *v == Seat::Occupied).count();
+}
+
+// Applies a rule a generation at a time, until the seats stop changing.
+struct Generations {
+    current: State,
+    next: State,
+    rule: Rule,
+    generation: usize
+}
+
+impl Generations {
+    fn new(layout: State, rule: Rule) -> Generations {
+        let next = next_generation(&layout, rule);
+        return Generations { current: layout, next, rule, generation: 0 };
+    }
+}
+
+impl Simulation for Generations {
+    fn step(&mut self) {
+        let next = next_generation(&self.next, self.rule);
+
+        self.current = mem::replace(&mut self.next, next);
+        self.generation += 1;
+    }
+
+    fn steps(&self) -> usize {
+        return self.generation;
+    }
+
+    fn finished(&self) -> bool {
+        return self.next == self.current;
+    }
+
+    fn summary(&self) -> String {
+        return format!("generation {}, {} occupied", self.generation, occupied_seats(&self.current));
+    }
+
+    fn state(&self) -> String {
+        let caption = format!("Generation {}, {} occupied", self.generation, occupied_seats(&self.current));
+        return format!("{}\n{}", caption, Frame::from_grid(&self.current));
+    }
+
+    fn variables(&self) -> Vec<(&'static str, i64)> {
+        return vec![
+            ("generation", self.generation as i64),
+            ("occupied", occupied_seats(&self.current) as i64)
+        ];
+    }
+}
+
+fn rule_part1(point: &(usize, usize), seat: Seat, state: &State) -> Seat {
+    let occupied = occupied(point, state);
+
+    // Apply rules based on the current value and the
+    // number of occupied neighbours.
+    return match seat {
+        Seat::Occupied => {
+            if occupied >= 4 {
+                Seat::Unoccupied
+            } else {
+                seat
+            }
+        },
+        Seat::Unoccupied => {
+            if occupied == 0 {
+                Seat::Occupied
+            } else {
+                seat
+            }
+        }
+        _ => seat
+    };
+}
+
+fn rule_part2(point: &(usize, usize), seat: Seat, state: &State) -> Seat {
+    let occupied = occupied_in_distance(point, state);
+
+    // Apply rules based on the current value and the
+    // number of occupied neighbours.
+    return match seat {
+        Seat::Occupied => {
+            if occupied >= 5 {
+                Seat::Unoccupied
+            } else {
+                seat
+            }
+        },
+        Seat::Unoccupied => {
+            if occupied == 0 {
+                Seat::Occupied
+            } else {
+                seat
+            }
+        }
+        _ => seat
+    };
+}
+
+fn next_generation(state: &State, rule: Rule) -> State {
+    return state.map(|point, &seat| rule(&point, seat, state));
+}
+
+fn occupied(point: &(usize, usize), state: &State) -> usize {
+    return state.neighbours8(point.0, point.1)
+        .filter(|&p| state[p] == Seat::Occupied)
+        .count();
+}
+
+fn occupied_in_distance(point: &(usize, usize), state: &State) -> usize {
+    let mut count = 0;
+
+    // Look in each direction, starting from N.
+    for &(dx, dy) in NEIGHBOURS8.iter() {
+        count += seat_in_line(point, state, dx, dy);
+    }
+
+    return count;
+}
+
+fn seat_in_line(point: &(usize, usize), state: &State, dx: isize, dy: isize) -> usize {
+    // The first seat seen in this direction, skipping over floor.
+    let seat = state.ray(point.0, point.1, dx, dy)
+        .map(|(_, &seat)| seat)
+        .find(|&seat| seat != Seat::None);
+
+    return match seat {
+        Some(Seat::Occupied) => 1,
+        _ => 0
+    };
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = State;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<State> {
+        return parse_input(input);
+    }
+
+    fn part1(input: &State) -> Result<usize> {
+        return stable_occupied_seats(input.clone(), rule_part1, None);
+    }
+
+    fn part2(input: &State) -> Result<usize> {
+        return stable_occupied_seats(input.clone(), rule_part2, None);
+    }
+
+    fn visualise(input: &State, part: Part, renderer: &mut dyn Renderer) -> Result<()> {
+        let rule = match part {
+            Part::One => rule_part1 as Rule,
+            Part::Two => rule_part2
+        };
+
+        stable_occupied_seats(input.clone(), rule, Some(renderer))?;
+        return Ok(());
+    }
+
+    fn simulate(input: &State, part: Part) -> Result<Box<dyn Simulation>> {
+        let rule = match part {
+            Part::One => rule_part1 as Rule,
+            Part::Two => rule_part2
+        };
+
+        return Ok(Box::new(Generations::new(input.clone(), rule)));
+    }
+}
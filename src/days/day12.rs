@@ -1,7 +1,12 @@
 // Advent of Code 2020
 // Day 12
 
-use std::fs;
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::{Part, Solution};
+use crate::viz::{Frame, Renderer};
 
 #[cfg(test)]
 mod test_examples {
@@ -23,7 +28,7 @@ mod test_examples {
         };
 
         for i in input.trim().split('\n') {
-            let action = Action::from_str(i.trim());
+            let action = Action::from_str(i.trim()).unwrap();
             state = apply_part1(&action, &state);
         }
 
@@ -58,7 +63,7 @@ mod test_examples {
         };
 
         for i in input.trim().split('\n') {
-            let action = Action::from_str(i.trim());
+            let action = Action::from_str(i.trim()).unwrap();
             let new = apply_part2(&action, &state, &waypoint);
             state = new.0;
             waypoint = new.1;
@@ -75,13 +80,133 @@ mod test_examples {
     }
 }
 
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn invalid_action() {
+        let err = Day12::parse("F10\nX3").err().expect("No error returned.");
+        assert_eq!("Line 2, column 1: Invalid action: X", err.to_string());
+    }
+
+    #[test]
+    fn invalid_value() {
+        let err = Action::from_str("Fabc").err().expect("No error returned.");
+        assert_eq!("Column 2: Invalid value for F: abc", err.to_string());
+    }
+
+    #[test]
+    fn invalid_turn() {
+        let err = Action::from_str("L45").err().expect("No error returned.");
+        assert_eq!("Column 2: Invalid value for L: 45", err.to_string());
+
+        // The message names the action actually given.
+        let err = Action::from_str("R135").err().expect("No error returned.");
+        assert_eq!("Column 2: Invalid value for R: 135", err.to_string());
+    }
+
+    #[test]
+    fn render() {
+        let input = "F10\nR135\n";
+        let err = Day12::parse(input).err().expect("No error returned.");
+
+        assert_eq!("\
+error: Invalid value for R: 135
+ --> <input>:2:2
+  |
+2 | R135
+  |  ^^^
+", err.render(input));
+    }
+}
+
+#[cfg(test)]
+mod test_visualise {
+    use super::*;
+
+    use crate::viz::Headless;
+
+    #[test]
+    fn track_part1() {
+        let actions = Day12::parse("F10\nN3\nF7\nR90\nF11").unwrap();
+        let (ship, waypoints) = track(&actions, Part::One);
+
+        assert_eq!(vec![(0, 0), (10, 0), (10, -3), (17, -3), (17, -3), (17, 8)], ship);
+        assert!(waypoints.is_empty());
+    }
+
+    #[test]
+    fn track_part2() {
+        let actions = Day12::parse("F10\nN3\nF7\nR90\nF11").unwrap();
+        let (ship, waypoints) = track(&actions, Part::Two);
+
+        assert_eq!(vec![(0, 0), (100, -10), (100, -10), (170, -38), (170, -38), (214, 72)], ship);
+        assert_eq!(vec![(10, -1), (110, -11), (110, -14), (180, -42), (174, -28), (218, 82)], waypoints);
+    }
+
+    // In part 2, the waypoint is drawn where it is relative to the ship.
+    #[test]
+    fn waypoint_frames() {
+        let actions = Day12::parse("F10\nN3").unwrap();
+
+        let mut headless = Headless::new();
+        Day12::visualise(&actions, Part::Two, &mut headless).expect("Error visualising");
+
+        let last = &headless.frames[2];
+
+        assert_eq!((111, 15), (last.width(), last.height()));
+        assert_eq!(Some('S'), last.get(0, 14));
+        assert_eq!(Some('@'), last.get(100, 4));
+        assert_eq!(Some('W'), last.get(110, 0));
+
+        // The waypoint is only drawn where it is now.
+        assert_eq!(Some(' '), last.get(110, 3));
+    }
+
+    #[test]
+    fn frames() {
+        let actions = Day12::parse("F10\nN3\nF7\nR90\nF11").unwrap();
+
+        let mut headless = Headless::new();
+        Day12::visualise(&actions, Part::One, &mut headless).expect("Error visualising");
+
+        // The start, then one frame per action.
+        assert_eq!(6, headless.frames.len());
+        assert_eq!("Start", headless.frames[0].caption());
+        assert_eq!("Action 5 of 5: F11, distance 25", headless.frames[5].caption());
+
+        let last = &headless.frames[5];
+
+        assert_eq!((18, 12), (last.width(), last.height()));
+        assert_eq!(Some('S'), last.get(0, 3));
+        assert_eq!(Some('@'), last.get(17, 11));
+        assert_eq!(Some('.'), last.get(17, 5));
+        assert_eq!(Some(' '), last.get(5, 5));
+    }
+
+    // Tracks too big to show a square per position are scaled down.
+    #[test]
+    fn scaled() {
+        let actions = Day12::parse("F1000\nS500").unwrap();
+
+        let mut headless = Headless::new();
+        Day12::visualise(&actions, Part::One, &mut headless).expect("Error visualising");
+
+        let last = headless.frames.last().unwrap();
+
+        assert!(last.width() <= TRACK_WIDTH && last.height() <= TRACK_HEIGHT);
+        assert_eq!(Some('@'), last.get(last.width() - 1, last.height() - 1));
+    }
+}
+
 #[cfg(test)]
 mod test_action_part1 {
     use super::*;
 
     #[test]
     fn test_apply_n() {
-        let a = Action::from_str("N3");
+        let a = Action::from_str("N3").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -97,7 +222,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_s() {
-        let a = Action::from_str("S2");
+        let a = Action::from_str("S2").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -113,7 +238,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_w() {
-        let a = Action::from_str("W10");
+        let a = Action::from_str("W10").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -129,7 +254,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_e() {
-        let a = Action::from_str("E1");
+        let a = Action::from_str("E1").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -145,7 +270,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_l90() {
-        let a = Action::from_str("L90");
+        let a = Action::from_str("L90").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -167,7 +292,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_l180() {
-        let a = Action::from_str("L180");
+        let a = Action::from_str("L180").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -189,7 +314,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_r90() {
-        let a = Action::from_str("R90");
+        let a = Action::from_str("R90").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -211,7 +336,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_r180() {
-        let a = Action::from_str("R180");
+        let a = Action::from_str("R180").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -233,7 +358,7 @@ mod test_action_part1 {
 
     #[test]
     fn test_apply_f() {
-        let a = Action::from_str("F2");
+        let a = Action::from_str("F2").unwrap();
 
         let state = State {
             x: 5,
@@ -291,7 +416,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_n() {
-        let a = Action::from_str("N3");
+        let a = Action::from_str("N3").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -314,7 +439,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_s() {
-        let a = Action::from_str("S4");
+        let a = Action::from_str("S4").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -337,7 +462,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_w() {
-        let a = Action::from_str("W1");
+        let a = Action::from_str("W1").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -360,7 +485,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_e() {
-        let a = Action::from_str("E10");
+        let a = Action::from_str("E10").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -383,7 +508,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_l90() {
-        let a = Action::from_str("L90");
+        let a = Action::from_str("L90").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -415,7 +540,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_l180() {
-        let a = Action::from_str("L180");
+        let a = Action::from_str("L180").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -447,7 +572,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_r90() {
-        let a = Action::from_str("R90");
+        let a = Action::from_str("R90").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -479,7 +604,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_r180() {
-        let a = Action::from_str("R180");
+        let a = Action::from_str("R180").unwrap();
         let state = State {
             x: 5,
             y: 3,
@@ -511,7 +636,7 @@ mod test_action_part2 {
 
     #[test]
     fn test_apply_f() {
-        let a = Action::from_str("F2");
+        let a = Action::from_str("F2").unwrap();
 
         let state = State {
             x: 5,
@@ -554,7 +679,7 @@ enum Direction {
     West
 }
 
-enum Action {
+pub enum Action {
     N(u32),
     S(u32),
     E(u32),
@@ -564,32 +689,52 @@ enum Action {
     F(u32)
 }
 
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::N(i) => write!(f, "N{}", i),
+            Action::S(i) => write!(f, "S{}", i),
+            Action::E(i) => write!(f, "E{}", i),
+            Action::W(i) => write!(f, "W{}", i),
+            Action::L(i) => write!(f, "L{}", i),
+            Action::R(i) => write!(f, "R{}", i),
+            Action::F(i) => write!(f, "F{}", i)
+        }
+    }
+}
+
 impl Action {
-    fn from_str(s: &str) -> Action {
-        let i = match s[1..].parse() {
+    fn from_str(s: &str) -> Result<Action> {
+        let action = match s.chars().next() {
+            Some(c) => c,
+            None => return Err(Error::parse("Empty action"))
+        };
+
+        let value = &s[action.len_utf8()..];
+
+        let invalid_value = || {
+            return Error::parse(format!("Invalid value for {}: {}", action, value)).at_column(action.len_utf8() + 1).with_text(value);
+        };
+
+        let i = match value.parse() {
             Ok(n) => n,
-            Err(_) => panic!("Invalid value for action: {}", s)
-        };
-
-        match &s[..1] {
-            "N" => return Action::N(i),
-            "S" => return Action::S(i),
-            "E" => return Action::E(i),
-            "W" => return Action::W(i),
-            "L" => {
-                if i % 90 != 0 {
-                    panic!("Invalid value for L: {}", i);
-                }
-                return Action::L(i)
-            },
-            "R" => {
-                if i % 90 != 0 {
-                    panic!("Invalid value for L: {}", i);
-                }
-                return Action::R(i)
-            },
-            "F" => return Action::F(i),
-            _ => panic!("Invalid action: {}", &s[..1])
+            Err(_) => return Err(invalid_value())
+        };
+
+        // Turns must be a whole number of quarter turns.
+        if (action == 'L' || action == 'R') && i % 90 != 0 {
+            return Err(invalid_value());
+        }
+
+        match action {
+            'N' => return Ok(Action::N(i)),
+            'S' => return Ok(Action::S(i)),
+            'E' => return Ok(Action::E(i)),
+            'W' => return Ok(Action::W(i)),
+            'L' => return Ok(Action::L(i)),
+            'R' => return Ok(Action::R(i)),
+            'F' => return Ok(Action::F(i)),
+            _ => return Err(Error::parse(format!("Invalid action: {}", action)).at_column(1).with_text(action))
         }
     }
 }
@@ -711,78 +856,186 @@ fn manhattan_distance(a: &State, b: &State) -> i32 {
     return dx + dy;
 }
 
-fn part1() -> i32 {
-    let input = fs::read_to_string("data/day12.txt")
-        .expect("Could not read data/day12.txt");
+// A list of positions, as (east, south).
+type Positions = Vec<(i32, i32)>;
 
+// The ship's position at the start and after each action and, in
+// part 2, where the waypoint is at the same times.
+fn track(actions: &[Action], part: Part) -> (Positions, Positions) {
     let mut state = State {
         x: 0,
         y: 0,
         heading: Direction::East
     };
 
-    for i in input.trim().split('\n') {
-        let action = Action::from_str(i.trim());
-        state = apply_part1(&action, &state);
+    let mut waypoint = Waypoint {
+        x: 10,
+        y: -1
+    };
+
+    let mut positions = vec![(0, 0)];
+    let mut waypoints = Vec::new();
+
+    if part == Part::Two {
+        waypoints.push((waypoint.x, waypoint.y));
     }
 
-    return manhattan_distance(&state,
-        &State {
-            x: 0,
-            y: 0,
-            heading: Direction::East
-        });
+    for action in actions {
+        match part {
+            Part::One => state = apply_part1(action, &state),
+            Part::Two => {
+                let new = apply_part2(action, &state, &waypoint);
+                state = new.0;
+                waypoint = new.1;
+
+                waypoints.push((state.x + waypoint.x, state.y + waypoint.y));
+            }
+        }
+
+        positions.push((state.x, state.y));
+    }
+
+    return (positions, waypoints);
 }
 
-fn part2() -> i32 {
-    let input = fs::read_to_string("data/day12.txt")
-        .expect("Could not read data/day12.txt");
+// Largest size of frame the track is drawn in. Tracks which are
+// bigger than this are scaled down to fit.
+const TRACK_WIDTH: usize = 120;
+const TRACK_HEIGHT: usize = 50;
 
-    let mut state = State {
-        x: 0,
-        y: 0,
-        heading: Direction::East
-    };
+// Draws the ship's track, a frame per action: S where it started,
+// @ where it is, dots along the way, and W at the waypoint if there
+// is one. North is up.
+fn draw_track(actions: &[Action], positions: &[(i32, i32)], waypoints: &[(i32, i32)], renderer: &mut dyn Renderer) -> Result<()> {
+    let everywhere = || positions.iter().chain(waypoints);
 
-    let mut waypoint = Waypoint {
-        x: 10,
-        y: -1
+    let min_x = everywhere().map(|p| p.0).min().unwrap();
+    let max_x = everywhere().map(|p| p.0).max().unwrap();
+    let min_y = everywhere().map(|p| p.1).min().unwrap();
+    let max_y = everywhere().map(|p| p.1).max().unwrap();
+
+    // How many positions each square of the frame covers.
+    let span_x = (max_x - min_x) as usize + 1;
+    let span_y = (max_y - min_y) as usize + 1;
+    let scale = span_x.div_ceil(TRACK_WIDTH).max(span_y.div_ceil(TRACK_HEIGHT));
+
+    let square = |(x, y): (i32, i32)| ((x - min_x) as usize / scale, (y - min_y) as usize / scale);
+
+    let (width, height) = square((max_x, max_y));
+    let mut frame = Frame::new(width + 1, height + 1);
+
+    let start = square(positions[0]);
+    frame.set(start.0, start.1, 'S');
+
+    // The waypoint moves with the ship, so it's only drawn where it is now.
+    let with_waypoint = |mut shown: Frame, i: usize| {
+        if let Some(&waypoint) = waypoints.get(i) {
+            let (x, y) = square(waypoint);
+            shown.set(x, y, 'W');
+        }
+
+        return shown;
     };
 
-    for i in input.trim().split('\n') {
-        let action = Action::from_str(i.trim());
-        let new = apply_part2(&action, &state, &waypoint);
-        state = new.0;
-        waypoint = new.1;
+    renderer.render(&with_waypoint(frame.clone(), 0).with_focus(start.0, start.1).with_caption("Start"))?;
+
+    for (i, pair) in positions.windows(2).enumerate() {
+        let (from, to) = (square(pair[0]), square(pair[1]));
+
+        // Mark every square along the way, not just where the ship stops.
+        let steps = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1));
+
+        for step in 0..steps {
+            let along = |a: usize, b: usize| (a as f64 + (b as f64 - a as f64) * step as f64 / steps as f64).round() as usize;
+            let (x, y) = (along(from.0, to.0), along(from.1, to.1));
+
+            if frame.get(x, y) == Some(' ') {
+                frame.set(x, y, '.');
+            }
+        }
+
+        if from != start {
+            frame.set(from.0, from.1, '.');
+        }
+
+        let mut shown = with_waypoint(frame.clone(), i + 1);
+        shown.set(to.0, to.1, '@');
+
+        let distance = pair[1].0.abs() + pair[1].1.abs();
+        let caption = format!("Action {} of {}: {}, distance {}", i + 1, actions.len(), actions[i], distance);
+
+        renderer.render(&shown.with_focus(to.0, to.1).with_caption(caption))?;
+    }
+
+    return Ok(());
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Vec<Action>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Action>> {
+        let mut actions = Vec::new();
+
+        for line in parse::lines(input) {
+            actions.push(Action::from_str(line.text).map_err(|e| line.locate(e))?);
+        }
+
+        return Ok(actions);
     }
 
-    return manhattan_distance(&state,
-        &State {
+    fn part1(actions: &Vec<Action>) -> Result<i32> {
+        let mut state = State {
             x: 0,
             y: 0,
             heading: Direction::East
-        });
-}
+        };
 
-#[cfg(test)]
-mod test_puzzles {
-    use super::*;
+        for action in actions {
+            state = apply_part1(action, &state);
+        }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(508, part1());
+        return Ok(manhattan_distance(&state,
+            &State {
+                x: 0,
+                y: 0,
+                heading: Direction::East
+            }));
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(30761, part2());
-    }
-}
+    fn part2(actions: &Vec<Action>) -> Result<i32> {
+        let mut state = State {
+            x: 0,
+            y: 0,
+            heading: Direction::East
+        };
+
+        let mut waypoint = Waypoint {
+            x: 10,
+            y: -1
+        };
+
+        for action in actions {
+            let new = apply_part2(action, &state, &waypoint);
+            state = new.0;
+            waypoint = new.1;
+        }
 
-fn main() {
-    let d = part1();
-    println!("Part 1: The manhattan distance is: {}", d);
+        return Ok(manhattan_distance(&state,
+            &State {
+                x: 0,
+                y: 0,
+                heading: Direction::East
+            }));
+    }
 
-    let d = part2();
-    println!("Part 2: The manhattan distance is: {}", d);
+    fn visualise(actions: &Vec<Action>, part: Part, renderer: &mut dyn Renderer) -> Result<()> {
+        let (positions, waypoints) = track(actions, part);
+        return draw_track(actions, &positions, &waypoints, renderer);
+    }
 }
@@ -0,0 +1,230 @@
+// Advent of Code 2020
+// Day 13
+
+use std::cmp::Reverse;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    #[test]
+    fn example_part1() {
+        let input = "
+        939
+        7,13,x,x,59,x,31,19";
+
+        let (arrival, buses) = parse_buses(input).expect("Invalid input");
+
+        assert_eq!(939, arrival);
+
+        assert_eq!(59, earliest_bus(arrival, &buses).0);
+    }
+
+    #[test]
+    fn example_part2() {
+        let input = "
+        939
+        7,13,x,x,59,x,31,19";
+
+        let (arrival, buses) = parse_buses(input).expect("Invalid input");
+
+        assert_eq!(939, arrival);
+
+        assert_eq!(1068781, earliest_timestamp(&buses));
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn parse_with_none_missing() {
+        let input = "
+        100
+        7,11,13";
+
+        let (arrival, buses) = parse_buses(input).expect("Invalid input");
+
+        assert_eq!(100, arrival);
+
+        assert_eq!(true, buses.contains(&7));
+        assert_eq!(true, buses.contains(&11));
+        assert_eq!(true, buses.contains(&13));
+
+        assert_eq!(false, buses.contains(&5));
+    }
+
+    #[test]
+    fn parse_with_missing() {
+        let input = "
+        523
+        7,52,x,6";
+
+        let (arrival, buses) = parse_buses(input).expect("Invalid input");
+
+        assert_eq!(523, arrival);
+
+        assert_eq!(true, buses.contains(&7));
+        assert_eq!(true, buses.contains(&52));
+        assert_eq!(true, buses.contains(&6));
+
+        assert_eq!(false, buses.contains(&11));
+    }
+
+    #[test]
+    fn missing_schedule() {
+        let err = parse_buses("939").expect_err("No error returned.");
+        assert_eq!("Line 2: No bus schedule in input", err.to_string());
+    }
+
+    #[test]
+    fn invalid_bus() {
+        let err = parse_buses("939\n7,13,y,59").expect_err("No error returned.");
+        assert_eq!("Line 2, column 6: Bus ID is not a valid integer: y", err.to_string());
+    }
+}
+
+fn parse_buses(input: &str) -> Result<(u32, Vec<u32>)> {
+    let mut lines = parse::lines(input);
+
+    let arrival = match lines.next() {
+        Some(a) => a,
+        None => return Err(Error::parse("No arrival in input").at_line(1))
+    };
+
+    let arrival_line = arrival.number;
+    let arrival = match arrival.text.parse() {
+        Ok(a) => a,
+        Err(_) => {
+            let err = Error::parse(format!("Arrival not a valid integer: {}", arrival.text));
+            return Err(arrival.locate(err.at_column(1).with_text(arrival.text)));
+        }
+    };
+
+    let schedule = match lines.next() {
+        Some(s) => s,
+        None => return Err(Error::parse("No bus schedule in input").at_line(arrival_line + 1))
+    };
+
+    // Buses which aren't in service are given ID 0.
+    let buses = parse::separated(schedule.text, ',', |b| {
+        if b == "x" {
+            return Ok(0);
+        }
+
+        return b.parse().map_err(|_| Error::parse(format!("Bus ID is not a valid integer: {}", b)).with_text(b));
+    });
+
+    return buses.map_err(|e| schedule.locate(e)).map(|buses| (arrival, buses));
+}
+
+fn earliest_bus(arrival: u32, buses: &[u32]) -> (u32, u32) {
+    let mut closest = u32::MAX;
+    let mut id = 0;
+    for &bus in buses {
+        if bus == 0 { continue; }
+
+        let departure = next_departure(arrival, bus);
+        if departure < closest {
+            closest = departure;
+            id = bus;
+        }
+    }
+
+    return (id, closest);
+}
+
+fn earliest_timestamp(buses: &[u32]) -> u128 {
+    // Only check multiples of the first entry in
+    // the schedule.
+    let first = buses[0] as u128;
+    let mut t = 0;
+
+    let mut timings = Vec::new();
+
+    for (i, bus) in buses[1..].iter().enumerate() {
+        if *bus == 0 {
+            continue;
+        }
+
+        timings.push((*bus as u128, (i as u128) + 1));
+    }
+
+    timings.sort_by_key(|&(bus, _)| Reverse(bus));
+
+    loop {
+        if satisfies(t, &timings) {
+            return t;
+        }
+
+        t += first;
+    }
+}
+
+fn satisfies(t: u128, buses: &[(u128, u128)]) -> bool {
+    for (bus, i) in buses {
+        if *bus == 0 {
+            continue;
+        }
+
+        let this_t = t + i;
+
+        if !this_t.is_multiple_of(*bus) {
+            return false;
+        }
+    }
+
+    return true;
+}
+
+fn next_departure(arrival: u32, id: u32) -> u32 {
+    let mut departure = id;
+    while departure < arrival {
+        departure += id;
+    }
+
+    return departure;
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = (u32, Vec<u32>);
+    type Answer1 = u32;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<(u32, Vec<u32>)> {
+        return parse_buses(input);
+    }
+
+    fn part1(input: &(u32, Vec<u32>)) -> Result<u32> {
+        let (arrival, buses) = input;
+
+        let next = earliest_bus(*arrival, buses);
+
+        // Bus 0 means there was no bus in service.
+        if next.0 == 0 {
+            return Err(Error::NoSolution(String::from("No buses in service")));
+        }
+
+        return Ok((next.1 - arrival) * next.0);
+    }
+
+    fn part2(input: &(u32, Vec<u32>)) -> Result<u128> {
+        // Only multiples of the first bus are checked,
+        // so it must be in service.
+        match input.1.first() {
+            Some(&bus) if bus != 0 => {},
+            _ => return Err(Error::InvalidState(String::from("First bus in schedule is not in service")))
+        }
+
+        return Ok(earliest_timestamp(&input.1));
+    }
+}
@@ -1,12 +1,14 @@
 // Advent of Code 2020
 // Day 14
 
-extern crate regex;
 use regex::Regex;
 
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
-use std::fs;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
 
 type Memory = HashMap<u64, u64>; 
 
@@ -22,7 +24,7 @@ mod test_examples {
         mem[7] = 101
         mem[8] = 0";
 
-        let memory = interpret(input);
+        let memory = interpret(&parse_program(input).expect("Invalid program"));
 
         assert_eq!(64, *memory.get(&8).unwrap());
         assert_eq!(101, *memory.get(&7).unwrap())
@@ -36,7 +38,7 @@ mod test_examples {
         mask = 00000000000000000000000000000000X0XX
         mem[26] = 1";
 
-        let memory = interpret_part2(input);
+        let memory = interpret_part2(&parse_program(input).expect("Invalid program"));
         
         assert_eq!(10, memory.len());
 
@@ -150,24 +152,113 @@ mod test_bitmask_address {
 }
 
 #[cfg(test)]
-mod test_puzzles {
+mod test_parse {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        assert_eq!(2346881602152, part1());
+    fn invalid_mask() {
+        let err = parse_program("mem[8] = 11\nmask = XX1").err().expect("No error returned.");
+        assert_eq!("Line 2, column 8: Invalid mask length", err.to_string());
+
+        let err = parse_program("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXY").err().expect("No error returned.");
+        assert_eq!("Line 1, column 43: Invalid character in mask: Y", err.to_string());
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(3885232834169, part2());
+    fn invalid_instruction() {
+        let err = parse_program("mem[8] = 11\nmemory[7] = 101").err().expect("No error returned.");
+        assert_eq!("Line 2, column 1: Invalid instruction: memory[7]", err.to_string());
+    }
+
+    #[test]
+    fn invalid_value() {
+        let err = parse_program("mem[8] = eleven").err().expect("No error returned.");
+        assert_eq!("Line 1, column 10: Invalid value: eleven", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_properties {
+    use super::*;
+
+    use crate::gen::Rng;
+    use crate::property;
+
+    // Every value whose set bits are a subset of <bits>.
+    fn possibilities_reference(bits: u64) -> HashSet<u64> {
+        let floating: Vec<u64> = (0..64).filter(|i| bits & (1 << i) != 0).collect();
+
+        return (0..1u64 << floating.len())
+            .map(|subset| {
+                return floating.iter()
+                    .enumerate()
+                    .filter(|&(i, _)| subset & (1 << i) != 0)
+                    .fold(0, |value, (_, bit)| value | (1 << bit));
+            })
+            .collect();
+    }
+
+    // Masks of 36 bits with only a few set, as in real programs.
+    fn floating(rng: &mut Rng) -> u64 {
+        return (0..rng.range(1, 9)).fold(0, |bits, _| bits | (1 << rng.below(36)));
+    }
+
+    #[test]
+    fn possibilities_match_reference() {
+        property::assert_holds(500, 14, floating, |&bits| {
+            // With no floating bits, find_possibilities isn't used.
+            if bits == 0 {
+                return Ok(());
+            }
+
+            let expected = possibilities_reference(bits);
+            let actual = find_possibilities(bits, msb(bits));
+
+            return if expected == actual {
+                Ok(())
+            } else {
+                Err(format!("Bits {:b}: expected {} possibilities, got {}", bits, expected.len(), actual.len()))
+            };
+        });
+    }
+
+    // Applying a mask to an address should give every address
+    // with the mask's 1s set and its Xs set either way.
+    #[test]
+    fn addresses_match_reference() {
+        let generate = |rng: &mut Rng| ((floating(rng), rng.below(1 << 36)), rng.below(1 << 36));
+
+        property::assert_holds(200, 15, generate, |&((bits, ones), address)| {
+            // Floating bits take priority over 1s.
+            let ones = ones & !bits;
+
+            let mask: String = (0..36).rev().map(|i| {
+                return match (bits & (1 << i) != 0, ones & (1 << i) != 0) {
+                    (true, _) => 'X',
+                    (false, true) => '1',
+                    (false, false) => '0'
+                };
+            }).collect();
+
+            let expected: HashSet<u64> = possibilities_reference(bits).iter()
+                .map(|p| ((address | ones) & !bits) | p)
+                .collect();
+
+            let actual = Mask::from_str(&mask).unwrap().addresses(address);
+
+            return if expected == actual {
+                Ok(())
+            } else {
+                Err(format!("Mask {} on {}: expected {:?}, got {:?}", mask, address, expected, actual))
+            };
+        });
     }
 }
 
 // A "mask" is actually a set of two bitmasks.
 // One bitmask is the "set" mask; this is ORed with the input.
 // The other is the "reset" mask; this is ANDed with the input.
-struct Mask {
+pub struct Mask {
     set: u64,   // Holds a 1 in any bit to be set, 0 elsewhere.
     reset: u64  // Holds a 0 in any bit to be reset, 1 elsewhere.
 }
@@ -187,16 +278,16 @@ impl Mask {
         };
     }
 
-    fn from_str(s: &str) -> Result<Mask, String> {
+    fn from_str(s: &str) -> Result<Mask> {
         // Return error if the string length is not
         // exactly 36.
         if s.len() != 36 {
-            return Err(String::from("Invalid mask length"))
+            return Err(Error::parse("Invalid mask length"))
         }
 
-        for c in s.chars() {
+        for (i, c) in s.char_indices() {
             if c != 'X' && c != '1' && c != '0' {
-                return Err(format!("Invalid character in mask: {}", c));
+                return Err(Error::parse(format!("Invalid character in mask: {}", c)).at_column(i + 1).with_text(c));
             }
         }
 
@@ -267,7 +358,7 @@ impl Mask {
         let possibilities = if highest < 64 {
             find_possibilities(floating, highest)
         } else {
-            HashSet::from_iter(vec![value].iter().map(|i| *i))
+            HashSet::from_iter(vec![value])
         };
 
         let mut addresses = HashSet::new();
@@ -329,124 +420,135 @@ fn msb(bits: u64) -> u64 {
     return msb;
 }
 
-fn interpret(input: &str) -> Memory {
-    // Regex to match memory stores.
-    let re = Regex::new(r"mem\[(\d+)\]").unwrap();
+// A single line of the initialisation program.
+pub enum Command {
+    // mask = <mask>
+    SetMask(Mask),
 
-    let mut memory = Memory::new();
+    // mem[<address>] = <value>
+    Store { address: u64, value: u64 }
+}
 
-    let mut mask = Mask::default();
+fn parse_program(input: &str) -> Result<Vec<Command>> {
+    // Regex to match memory stores.
+    let re = Regex::new(r"^mem\[(\d+)\]$").unwrap();
 
-    for line in input.trim().split('\n') {
-        let line = line.trim();
+    let mut program = Vec::new();
 
-        // Split on "="
-        let sep_index = line.find('=')
-            .expect(&format!("Invalid line: {}", line));
+    for line in parse::lines(input) {
+        program.push(parse_command(&re, line.text).map_err(|e| line.locate(e))?);
+    }
 
-        let command = line.split_at(sep_index).0.trim();
+    return Ok(program);
+}
 
-        // split_at.1 includes the '='.
-        let value = line.split_at(sep_index).1[1..].trim();
+fn parse_command(re: &Regex, line: &str) -> Result<Command> {
+    // Split on "="
+    let sep_index = match line.find('=') {
+        Some(i) => i,
+        None => return Err(Error::parse(format!("Invalid line: {}", line)))
+    };
 
-        // Mask or store?
-        if command == "mask" {
-            mask = Mask::from_str(value).unwrap();
-        }
-        else {
-            // Attempt to match against regex.
-            // If match, extract the address.
-            let address = match re.captures(command) {
-                Some(captures) => {
-                    captures.get(1).unwrap()
-                            .as_str()
-                            .parse::<u64>().unwrap()
-                },
-                None => panic!("Invalid instruction: {}", command)
-            };
+    let command = line.split_at(sep_index).0.trim();
 
-            let value = value.parse().unwrap();
+    // split_at.1 includes the '='.
+    let value = line.split_at(sep_index).1[1..].trim();
 
-            memory.insert(address, mask.apply(value));
-        }
+    // Mask or store?
+    if command == "mask" {
+        // Errors in the mask are placed within the line.
+        let mask = Mask::from_str(value).map_err(|e| {
+            return e.at_column(1).with_text(value).shift_column(parse::column_of(line, value) - 1);
+        })?;
 
+        return Ok(Command::SetMask(mask));
     }
 
-    return memory;
-}
+    // Attempt to match against regex.
+    // If match, extract the address.
+    let address = match re.captures(command) {
+        Some(captures) => {
+            let address = captures.get(1).unwrap().as_str();
+
+            match address.parse::<u64>() {
+                Ok(a) => a,
+                Err(_) => {
+                    let err = Error::parse(format!("Invalid address: {}", address));
+                    return Err(err.at_column(parse::column_of(line, address)).with_text(address));
+                }
+            }
+        },
+        None => return Err(Error::parse(format!("Invalid instruction: {}", command)).at_column(1).with_text(command))
+    };
 
-fn interpret_part2(input: &str) -> Memory {
-    // Regex to match memory stores.
-    let re = Regex::new(r"mem\[(\d+)\]").unwrap();
+    let value = match value.parse() {
+        Ok(v) => v,
+        Err(_) => return Err(Error::parse(format!("Invalid value: {}", value)).at_column(parse::column_of(line, value)).with_text(value))
+    };
 
-    let mut memory = Memory::new();
-
-    let mut mask = Mask::default_address();
+    return Ok(Command::Store { address, value });
+}
 
-    for line in input.trim().split('\n') {
-        let line = line.trim();
+fn interpret(program: &[Command]) -> Memory {
+    let mut memory = Memory::new();
 
-        // Split on "="
-        let sep_index = line.find('=')
-            .expect(&format!("Invalid line: {}", line));
+    let mut mask = &Mask::default();
 
-        let command = line.split_at(sep_index).0.trim();
+    for command in program {
+        match command {
+            Command::SetMask(m) => mask = m,
+            Command::Store { address, value } => {
+                memory.insert(*address, mask.apply(*value));
+            }
+        }
+    }
 
-        // split_at.1 includes the '='.
-        let value = line.split_at(sep_index).1[1..].trim();
+    return memory;
+}
 
-        // Mask or store?
-        if command == "mask" {
-            mask = Mask::from_str(value).unwrap();
-        }
-        else {
-            // Attempt to match against regex.
-            // If match, extract the address.
-            let address = match re.captures(command) {
-                Some(captures) => {
-                    captures.get(1).unwrap()
-                            .as_str()
-                            .parse::<u64>().unwrap()
-                },
-                None => panic!("Invalid instruction: {}", command)
-            };
+fn interpret_part2(program: &[Command]) -> Memory {
+    let mut memory = Memory::new();
 
-            let value = value.parse().unwrap();
+    let mut mask = &Mask::default_address();
 
-            let addresses = mask.addresses(address);
+    for command in program {
+        match command {
+            Command::SetMask(m) => mask = m,
+            Command::Store { address, value } => {
+                let addresses = mask.addresses(*address);
 
-            for a in &addresses {
-                memory.insert(*a, value);
+                for a in &addresses {
+                    memory.insert(*a, *value);
+                }
             }
         }
-
     }
 
     return memory;
 }
 
-fn part1() -> u64 {
-    let input = fs::read_to_string("data/day14.txt")
-        .expect("Could not read data/day14.txt");
+pub struct Day14;
 
-    let memory = interpret(&input);
+impl Solution for Day14 {
+    const DAY: u32 = 14;
 
-    return memory.values().sum();
-}
+    type Input = Vec<Command>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-fn part2() -> u64 {
-    let input = fs::read_to_string("data/day14.txt")
-        .expect("Could not read data/day14.txt");
+    fn parse(input: &str) -> Result<Vec<Command>> {
+        return parse_program(input);
+    }
 
-    let memory = interpret_part2(&input);
+    fn part1(program: &Vec<Command>) -> Result<u64> {
+        let memory = interpret(program);
 
-    return memory.values().sum();
-}
+        return Ok(memory.values().sum());
+    }
 
-fn main() {
-    let sum_part1 = part1();
-    println!("Part 1: The sum of all values in memory is: {}", sum_part1);
+    fn part2(program: &Vec<Command>) -> Result<u64> {
+        let memory = interpret_part2(program);
 
-    let sum_part2 = part2();
-    println!("Part 2: The sum of all values in memory is: {}", sum_part2);
+        return Ok(memory.values().sum());
+    }
 }
@@ -0,0 +1,263 @@
+// Advent of Code 2020
+// Day 15
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::debug::Simulation;
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::{Part, Solution};
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    #[test]
+    fn part1() {
+        let input = vec![0, 3, 6];
+        assert_eq!(0, spoken(&input, 1));
+        assert_eq!(3, spoken(&input, 2));
+        assert_eq!(6, spoken(&input, 3));
+
+        assert_eq!(436, spoken(&input, 2020));
+    }
+}
+
+#[cfg(test)]
+mod test_spoken {
+    use super::*;
+
+    #[test]
+    fn starting() {
+        let input = vec![4, 6, 2];
+        assert_eq!(4, spoken(&input, 1));
+        assert_eq!(6, spoken(&input, 2));
+        assert_eq!(2, spoken(&input, 3));
+    }
+
+    #[test]
+    fn fourth() {
+        let input = vec![0, 3, 6];
+        assert_eq!(0, spoken(&input, 4));
+    }
+
+    #[test]
+    fn fifth() {
+        let input = vec![0, 3, 6];
+        assert_eq!(3, spoken(&input, 5));
+    }
+
+    #[test]
+    fn sixth() {
+        let input = vec![0, 3, 6];
+        assert_eq!(3, spoken(&input, 6));
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn invalid_number() {
+        let err = Day15::parse("0,3,six").expect_err("No error returned.");
+        assert_eq!("Line 1, column 5: Starting number is not a valid integer: six", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_simulate {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        let starting = vec![0, 3, 6];
+        let mut turns = Day15::simulate(&starting, Part::One).unwrap();
+
+        assert_eq!("turn 0, next 0", turns.summary());
+
+        for _ in 0..4 {
+            turns.step();
+        }
+
+        assert_eq!("turn 4, spoken 0, next 3", turns.summary());
+        assert_eq!(vec![("turn", 4), ("spoken", 0), ("next", 3)], turns.variables());
+        assert_eq!("Turn 4: 0\nLast 4 spoken: 0, 3, 6, 0\nNext: 3\n", turns.state());
+
+        while !turns.finished() {
+            turns.step();
+        }
+
+        assert_eq!(2020, turns.steps());
+        assert_eq!(Some(("spoken", 436)), turns.variables().get(1).copied());
+    }
+
+    // Only the most recent numbers are kept to show.
+    #[test]
+    fn history() {
+        let starting = vec![0, 3, 6];
+        let mut turns = Day15::simulate(&starting, Part::One).unwrap();
+
+        for _ in 0..12 {
+            turns.step();
+        }
+
+        assert!(turns.state().contains("Last 10 spoken: 6, 0, 3, 3, 1, 0, 4, 0, 2, 0\n"));
+    }
+}
+
+// The memory game, a turn at a time.
+struct Game {
+    starting: Vec<u32>,
+    turn: usize,
+    spoken: u32,
+    said: HashMap<u32, usize>,
+    said_twice: HashMap<u32, (usize, usize)>
+}
+
+impl Game {
+    fn new(starting: &[u32]) -> Game {
+        return Game {
+            starting: starting.to_vec(),
+            turn: 0,
+            spoken: *starting.last().unwrap(),
+            said: HashMap::new(),
+            said_twice: HashMap::new()
+        };
+    }
+
+    // The number to be spoken on the next turn.
+    fn next(&self) -> u32 {
+        let previous = self.spoken;
+
+        return if self.turn < self.starting.len() {
+            self.starting[self.turn]
+        } else if self.said_twice.contains_key(&previous) {
+            (self.said_twice[&previous].0 - self.said_twice[&previous].1) as u32
+        } else {
+            0
+        };
+    }
+
+    fn take_turn(&mut self) {
+        let spoken = self.next();
+        self.turn += 1;
+
+        if self.said.contains_key(&spoken) {
+            self.said_twice.insert(spoken, (self.turn, self.said[&spoken]));
+        }
+
+        self.said.insert(spoken, self.turn);
+        self.spoken = spoken;
+    }
+}
+
+fn spoken(starting: &[u32], i: usize) -> u32 {
+    assert!(i != 0);
+
+    let mut game = Game::new(starting);
+
+    for _ in 0..i {
+        game.take_turn();
+    }
+
+    return game.spoken;
+}
+
+// Numbers kept to show when stepping through the game.
+const HISTORY: usize = 10;
+
+// Plays the game up to the turn a part asks for, keeping
+// the most recent numbers spoken.
+struct Turns {
+    game: Game,
+    last: VecDeque<u32>,
+    until: usize
+}
+
+impl Simulation for Turns {
+    fn step(&mut self) {
+        self.game.take_turn();
+
+        if self.last.len() == HISTORY {
+            self.last.pop_front();
+        }
+
+        self.last.push_back(self.game.spoken);
+    }
+
+    fn steps(&self) -> usize {
+        return self.game.turn;
+    }
+
+    fn finished(&self) -> bool {
+        return self.game.turn >= self.until;
+    }
+
+    fn summary(&self) -> String {
+        if self.game.turn == 0 {
+            return format!("turn 0, next {}", self.game.next());
+        }
+
+        return format!("turn {}, spoken {}, next {}", self.game.turn, self.game.spoken, self.game.next());
+    }
+
+    fn state(&self) -> String {
+        let last: Vec<String> = self.last.iter().map(|n| n.to_string()).collect();
+
+        return format!("Turn {}: {}\nLast {} spoken: {}\nNext: {}\n",
+            self.game.turn, self.game.spoken, last.len(), last.join(", "), self.game.next());
+    }
+
+    fn variables(&self) -> Vec<(&'static str, i64)> {
+        return vec![
+            ("turn", self.game.turn as i64),
+            ("spoken", self.game.spoken as i64),
+            ("next", self.game.next() as i64)
+        ];
+    }
+}
+
+// The turns each part asks for the number spoken on.
+const PART1_TURNS: usize = 2020;
+const PART2_TURNS: usize = 30000000;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>> {
+        let line = match parse::lines(input).next() {
+            Some(line) => line,
+            None => return Err(Error::parse("No starting numbers in input"))
+        };
+
+        let starting = parse::separated(line.text, ',', |n| {
+            return n.parse().map_err(|_| Error::parse(format!("Starting number is not a valid integer: {}", n)).with_text(n));
+        });
+
+        return starting.map_err(|e| line.locate(e));
+    }
+
+    fn part1(input: &Vec<u32>) -> Result<u32> {
+        return Ok(spoken(input, PART1_TURNS));
+    }
+
+    fn part2(input: &Vec<u32>) -> Result<u32> {
+        return Ok(spoken(input, PART2_TURNS));
+    }
+
+    fn simulate(input: &Vec<u32>, part: Part) -> Result<Box<dyn Simulation>> {
+        let until = match part {
+            Part::One => PART1_TURNS,
+            Part::Two => PART2_TURNS
+        };
+
+        return Ok(Box::new(Turns { game: Game::new(input), last: VecDeque::new(), until }));
+    }
+}
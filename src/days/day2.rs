@@ -0,0 +1,1074 @@
+// Advent of Code 2020
+// Day 2
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+
+#[cfg(test)]
+mod tests_parse_rule {
+    use super::*;
+
+    fn clause(least: Option<usize>, most: Option<usize>, needle: Needle) -> Clause {
+        return Clause { bounds: Bounds { least, most }, needle };
+    }
+
+    // Test that we can parse a rule.
+    #[test]
+    fn test_parse_rule() {
+        let r = parse_rule("1-3 a")
+            .expect("Expected valid rule to be parsed successfully");
+        
+        assert_eq!(vec![clause(Some(1), Some(3), Needle::Character('a'))], r.clauses);
+    }
+
+    // Ensure we raise an appropriate error with an invalid rule.
+    #[test]
+    fn test_parse_rule_nonsense() {
+        let err = parse_rule("NotARule").expect_err("No error returned.");
+        assert_eq!("Column 1: Invalid rule.", err.to_string());
+    }
+
+    // Ensure we raise an appropriate error with just a range.
+    #[test]
+    fn test_parse_rule_nochar() {
+        let err = parse_rule("1-3").expect_err("No error returned.");
+        assert_eq!("Column 1: Invalid rule.", err.to_string());
+    }
+
+    // Ensure we raise an error if the range contains a non-integer.
+    #[test]
+    fn test_parse_rule_invalid_range_most() {
+        let err = parse_rule("1-bob a").expect_err("No error returned.");
+        assert_eq!("Column 3: Non-integer in range: bob", err.to_string());
+    }
+
+    // Ensure we raise an error if the range contains a non-integer.
+    #[test]
+    fn test_parse_rule_invalid_range_least() {
+        let err = parse_rule("alice-7 a").expect_err("No error returned.");
+        assert_eq!("Column 1: Non-integer in range: alice", err.to_string());
+    }
+
+    // Ensure we raise an error if the first half of the rule is not a range.
+    #[test]
+    fn test_parse_rule_notarange() {
+        let err = parse_rule("fff a").expect_err("No error returned.");
+        assert_eq!("Column 1: First component of rule is not a range.", err.to_string());
+    }
+
+    // Several characters in the second half of the rule make a substring.
+    #[test]
+    fn test_parse_rule_multiple_chars() {
+        let r = parse_rule("1-3 abc").expect("Valid rule rejected");
+        assert_eq!(vec![clause(Some(1), Some(3), Needle::Substring(String::from("abc")))], r.clauses);
+    }
+
+    // Ensure we raise an error if the rule contains a space but no second half,
+    // i.e. the last character is a space.
+    #[test]
+    fn test_parse_rule_space_at_end() {
+        let err = parse_rule("1-3 ").expect_err("No error returned.");
+        assert_eq!("Column 5: Second component of rule must be a character, substring or class.", err.to_string());
+    }
+
+    #[test]
+    fn test_parse_rule_class() {
+        let r = parse_rule("2-4 [0-9_a-]").expect("Valid rule rejected");
+        let ranges = vec![('0', '9'), ('_', '_'), ('a', 'a'), ('-', '-')];
+        assert_eq!(vec![clause(Some(2), Some(4), Needle::Class(ranges))], r.clauses);
+    }
+
+    #[test]
+    fn test_parse_rule_open_bounds() {
+        let r = parse_rule("3- x").expect("Valid rule rejected");
+        assert_eq!(vec![clause(Some(3), None, Needle::Character('x'))], r.clauses);
+
+        let r = parse_rule("-5 x").expect("Valid rule rejected");
+        assert_eq!(vec![clause(None, Some(5), Needle::Character('x'))], r.clauses);
+
+        let err = parse_rule("- x").expect_err("No error returned.");
+        assert_eq!("Column 1: Range must have at least one bound.", err.to_string());
+    }
+
+    #[test]
+    fn test_parse_rule_clauses() {
+        let r = parse_rule("1-3 ab; 2- [;]").expect("Valid rule rejected");
+        let expected = vec![
+            clause(Some(1), Some(3), Needle::Substring(String::from("ab"))),
+            clause(Some(2), None, Needle::Class(vec![(';', ';')]))
+        ];
+        assert_eq!(expected, r.clauses);
+    }
+
+    // Only a class needle can hold a ';', so brackets elsewhere
+    // don't stop a rule splitting into clauses.
+    #[test]
+    fn test_parse_rule_brackets_in_substring() {
+        let r = parse_rule("1-3 a[b; 2- c").expect("Valid rule rejected");
+        let expected = vec![
+            clause(Some(1), Some(3), Needle::Substring(String::from("a[b"))),
+            clause(Some(2), None, Needle::Character('c'))
+        ];
+        assert_eq!(expected, r.clauses);
+
+        let r = parse_rule("1-3 ]; 2- [x;]").expect("Valid rule rejected");
+        let expected = vec![
+            clause(Some(1), Some(3), Needle::Character(']')),
+            clause(Some(2), None, Needle::Class(vec![('x', 'x'), (';', ';')]))
+        ];
+        assert_eq!(expected, r.clauses);
+    }
+
+    // Errors in later clauses point to the right column of the whole rule.
+    #[test]
+    fn test_parse_rule_clause_errors() {
+        let err = parse_rule("1-3 a; 2-4").expect_err("No error returned.");
+        assert_eq!("Column 8: Invalid rule.", err.to_string());
+
+        let err = parse_rule("1-3 a; 2-x b").expect_err("No error returned.");
+        assert_eq!("Column 10: Non-integer in range: x", err.to_string());
+
+        let err = parse_rule("1-3 a;; 2- b").expect_err("No error returned.");
+        assert_eq!("Column 7: Empty clause in rule.", err.to_string());
+
+        let err = parse_rule("1-3 a; 2- [a-z").expect_err("No error returned.");
+        assert_eq!("Column 11: Character class is not closed.", err.to_string());
+
+        let err = parse_rule("1-3 [z-a]").expect_err("No error returned.");
+        assert_eq!("Column 5: Invalid range in character class: z-a", err.to_string());
+
+        let err = parse_rule("1-3 []").expect_err("No error returned.");
+        assert_eq!("Column 5: Character class is empty.", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests_password_parsing {
+    use super::*;
+
+    // Ensure we raise an appropriate error if the string cannot be parsed
+    // into a rule and a password.
+    #[test]
+    fn test_cannot_parse() {
+        let err = parse_password("1-3 a blah").expect_err("No error returned.");
+        assert_eq!("Line must contain a rule and password, separated by ':'.", err.to_string());
+    }
+
+    // Ensure a valid password-rule pair can be parsed.
+    #[test]
+    fn test_valid() {
+        let password_and_rule = parse_password("1-3 a: blah").expect("No error returned.");
+
+        let bounds = Bounds { least: Some(1), most: Some(3) };
+        assert_eq!(vec![Clause { bounds, needle: Needle::Character('a') }], password_and_rule.0.clauses);
+
+        assert_eq!("blah", password_and_rule.1);
+    }
+
+    // Errors in the puzzle input say which line they are on.
+    #[test]
+    fn test_error_line() {
+        let err = Day2::parse("1-3 a: abcde\n1-x b: cdefg").expect_err("No error returned.");
+        assert_eq!("Line 2, column 3: Non-integer in range: x", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests_password_valid {
+    use super::*;
+
+    // These tests predate multi-clause rules, so they build and take
+    // apart rules with a single character in a single clause.
+    fn simple_rule(a: usize, b: usize, character: char) -> Rule {
+        let bounds = Bounds { least: Some(a), most: Some(b) };
+        return Rule { clauses: vec![Clause { bounds, needle: Needle::Character(character) }] };
+    }
+
+    fn parts(r: &Rule) -> (usize, usize, char) {
+        return match r.clauses.as_slice() {
+            [Clause { bounds: Bounds { least: Some(a), most: Some(b) }, needle: Needle::Character(c) }] => (*a, *b, *c),
+            _ => panic!("Not a simple rule: {:?}", r)
+        };
+    }
+
+    // Test that we mark a password as invalid
+    // when there are too few of the required character.
+    #[test]
+    fn test_too_few() {
+        let r = simple_rule(2, 4, 'a');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let count = p.matches(character).count();
+
+            if count >= a && count <= b {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(false, password_valid(&r, f, "abc"));
+    }
+
+    // Test that we mark a password as invalid
+    // when there are too many of the required character.
+    #[test]
+    fn test_too_many() {
+        let r = simple_rule(2, 4, 'a');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let count = p.matches(character).count();
+
+            if count >= a && count <= b {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(false, password_valid(&r, f, "abracadabra"));
+    }
+
+    // Test that we mark a password as valid
+    // when there are exactly the least number
+    // of the required character.
+    #[test]
+    fn test_exactly_least() {
+        let r = simple_rule(2, 4, 'd');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let count = p.matches(character).count();
+
+            if count >= a && count <= b {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(true, password_valid(&r, f, "dado"));
+    }
+
+    // Test that we mark a password as valid
+    // when there are exactly the most number
+    // of the required character.
+    #[test]
+    fn test_exactly_most() {
+        let r = simple_rule(2, 4, 'd');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let count = p.matches(character).count();
+
+            if count >= a && count <= b {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(true, password_valid(&r, f, "dadodd"));
+    }
+
+    // Test that we mark a password as valid
+    // when there are between least and most
+    // of the required character.
+    #[test]
+    fn test_somewhere_inbetween() {
+        let r = simple_rule(2, 4, 'e');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let count = p.matches(character).count();
+
+            if count >= a && count <= b {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(true, password_valid(&r, f, "eee"));
+    }
+
+    // Test that we can apply different functions using
+    // rules to a password.
+    #[test]
+    fn test_different_functions() {
+        let r = simple_rule(2, 4, 'e');
+
+        let f1 = |r1: &Rule, p1: &str| {
+            let (a, b, character) = parts(r1);
+
+            let count = p1.matches(character).count();
+
+            if count >= a && count <= b {
+                return true;
+            }
+
+            return false;
+        };
+
+        // Valid by first rule but not second.
+        assert_eq!(true, password_valid(&r, f1, "ebee"));
+
+        let f2 = |r2: &Rule, p2: &str| {
+            let (a, b, character) = parts(r2);
+
+            let at_a = if a < p2.len() - 1 {
+                p2.chars().nth(a - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            let at_b = if b < p2.len() - 1 {
+                p2.chars().nth(b - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            if at_a && !at_b {
+                return true;
+            }
+
+            if at_b && !at_a {
+                return true;
+            }
+
+            return false;
+        };
+
+        // Valid by second rule but not first.
+        assert_eq!(true, password_valid(&r, f2, "bedcc"));
+    }
+
+    // One of the examples for part 2.
+    #[test]
+    fn test_example_part2_a() {
+        let r = simple_rule(1, 3, 'a');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let at_a = if a < p.len() - 1 {
+                p.chars().nth(a - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            let at_b = if b < p.len() - 1 {
+                p.chars().nth(b - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            if at_a && !at_b {
+                return true;
+            }
+
+            if at_b && !at_a {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(true, password_valid(&r, f, "abcde"));
+    }
+
+    // Another of the examples for part 2.
+    #[test]
+    fn test_example_part2_b() {
+        let r = simple_rule(1, 3, 'b');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let at_a = if a < p.len() - 1 {
+                p.chars().nth(a - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            let at_b = if b < p.len() - 1 {
+                p.chars().nth(b - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            if at_a && !at_b {
+                return true;
+            }
+
+            if at_b && !at_a {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(false, password_valid(&r, f, "cdefg"));
+    }
+
+    // Another of the examples for part 2.
+    #[test]
+    fn test_example_part2_c() {
+        let r = simple_rule(2, 9, 'c');
+
+        let f = |r: &Rule, p: &str| {
+            let (a, b, character) = parts(r);
+
+            let at_a = if a <= p.len() {
+                p.chars().nth(a - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            let at_b = if b <= p.len() {
+                p.chars().nth(b - 1).unwrap() == character
+            } else {
+                false
+            };
+
+            if at_a && !at_b {
+                return true;
+            }
+
+            if at_b && !at_a {
+                return true;
+            }
+
+            return false;
+        };
+
+        assert_eq!(false, password_valid(&r, f, "ccccccccc"));
+    }
+}
+
+#[cfg(test)]
+mod tests_policy {
+    use super::*;
+
+    fn check(policy: &dyn Policy, line: &str) -> bool {
+        let (rule, password) = parse_password(line).unwrap();
+        return policy.valid(&rule, &password);
+    }
+
+    // The examples from the puzzle, under each policy.
+    #[test]
+    fn built_in() {
+        assert_eq!(true, check(&Sled, "1-3 a: abcde"));
+        assert_eq!(false, check(&Sled, "1-3 b: cdefg"));
+        assert_eq!(true, check(&Sled, "2-9 c: ccccccccc"));
+
+        assert_eq!(true, check(&Toboggan, "1-3 a: abcde"));
+        assert_eq!(false, check(&Toboggan, "1-3 b: cdefg"));
+        assert_eq!(false, check(&Toboggan, "2-9 c: ccccccccc"));
+    }
+
+    // Positions past the end of the password, or before its
+    // start, hold nothing.
+    #[test]
+    fn toboggan_out_of_range() {
+        assert_eq!(true, check(&Toboggan, "2-10 b: ab"));
+        assert_eq!(true, check(&Toboggan, "0-1 a: ab"));
+    }
+
+    // Substrings are counted without overlapping, and looked for
+    // starting at each position.
+    #[test]
+    fn substrings() {
+        assert_eq!(true, check(&Sled, "2-2 ab: abxab"));
+        assert_eq!(false, check(&Sled, "2-2 aa: aaa"));
+
+        assert_eq!(true, check(&Toboggan, "1-3 ab: abxab"));
+        assert_eq!(false, check(&Toboggan, "1-3 ab: abab"));
+        assert_eq!(true, check(&Toboggan, "1-3 ab: abba"));
+        assert_eq!(false, check(&Toboggan, "3-5 ab: xxa"));
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(true, check(&Sled, "2-4 [0-9]: a1b2c3"));
+        assert_eq!(false, check(&Sled, "2-4 [0-9]: a1bc"));
+
+        assert_eq!(true, check(&Toboggan, "1-2 [a-c]: bx"));
+        assert_eq!(false, check(&Toboggan, "1-2 [a-c]: ba"));
+    }
+
+    // An open bound leaves the count unlimited on that side, or is
+    // a position that never matches.
+    #[test]
+    fn open_bounds() {
+        assert_eq!(true, check(&Sled, "3- x: xxxxxx"));
+        assert_eq!(false, check(&Sled, "3- x: xx"));
+        assert_eq!(true, check(&Sled, "-1 x: abc"));
+        assert_eq!(false, check(&Sled, "-1 x: xx"));
+
+        assert_eq!(true, check(&Toboggan, "2- x: ax"));
+        assert_eq!(false, check(&Toboggan, "-1 x: ax"));
+    }
+
+    // Every clause must hold.
+    #[test]
+    fn clauses() {
+        assert_eq!(true, check(&Sled, "1- [0-9]; 1- [A-Z]: Passw0rd"));
+        assert_eq!(false, check(&Sled, "1- [0-9]; 1- [A-Z]: passw0rd"));
+
+        assert_eq!(true, check(&Toboggan, "1-2 a; 3-4 b: axbx"));
+        assert_eq!(false, check(&Toboggan, "1-2 a; 3-4 b: axbb"));
+    }
+
+    #[test]
+    fn combined() {
+        let both = And(Box::new(Sled), Box::new(Toboggan));
+        let either = Or(Box::new(Sled), Box::new(Toboggan));
+        let only_sled = And(Box::new(Sled), Box::new(Not(Box::new(Toboggan))));
+
+        assert_eq!(true, check(&both, "1-3 a: abcde"));
+        assert_eq!(false, check(&both, "2-9 c: ccccccccc"));
+
+        assert_eq!(true, check(&either, "2-9 c: ccccccccc"));
+        assert_eq!(false, check(&either, "1-3 b: cdefg"));
+
+        assert_eq!(true, check(&only_sled, "2-9 c: ccccccccc"));
+        assert_eq!(false, check(&only_sled, "1-3 a: abcde"));
+    }
+
+    #[test]
+    fn parse() {
+        let policy = parse_policy("sled and not toboggan").expect("Valid policy rejected");
+        assert_eq!(true, check(policy.as_ref(), "2-9 c: ccccccccc"));
+        assert_eq!(false, check(policy.as_ref(), "1-3 a: abcde"));
+
+        // And binds more tightly than or.
+        let policy = parse_policy("not sled or sled and toboggan").expect("Valid policy rejected");
+        assert_eq!(true, check(policy.as_ref(), "1-3 b: cdefg"));
+        assert_eq!(true, check(policy.as_ref(), "1-3 a: abcde"));
+        assert_eq!(false, check(policy.as_ref(), "2-9 c: ccccccccc"));
+
+        let policy = parse_policy("!(sled|toboggan)").expect("Valid policy rejected");
+        assert_eq!(true, check(policy.as_ref(), "1-3 b: cdefg"));
+        assert_eq!(false, check(policy.as_ref(), "1-3 a: abcde"));
+
+        let policy = parse_policy("  SLED & Toboggan ").expect("Valid policy rejected");
+        assert_eq!(true, check(policy.as_ref(), "1-3 a: abcde"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse_policy("sled and luge").err().expect("No error returned.");
+        assert_eq!("Column 10: Unknown policy: luge (policies are sled, toboggan)", err.to_string());
+
+        let err = parse_policy("(sled or toboggan").err().expect("No error returned.");
+        assert_eq!("Column 18: Expected ')'", err.to_string());
+
+        let err = parse_policy("sled toboggan").err().expect("No error returned.");
+        assert_eq!("Column 6: Unexpected toboggan", err.to_string());
+
+        let err = parse_policy("sled and").err().expect("No error returned.");
+        assert_eq!("Column 9: Expected a policy", err.to_string());
+
+        let err = parse_policy("").err().expect("No error returned.");
+        assert_eq!("Column 1: Expected a policy", err.to_string());
+    }
+
+    #[test]
+    fn one_pass() {
+        let input = Day2::parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        let policies: Vec<Box<dyn Policy>> = vec![Box::new(Sled), Box::new(Toboggan), parse_policy("sled or toboggan").unwrap()];
+
+        assert_eq!(vec![2, 1, 2], count_valid(&input, &policies));
+    }
+}
+
+// Represents a password rule.
+//
+// A password rule is one or more clauses, separated by ';', each with
+// the following syntax:
+// <least>-<most> <needle>
+//
+// Where:
+// - <least> and <most> bound the needle, and either can be left out
+// - <needle> is a single character, a substring, or a class of
+//   characters in brackets such as [0-9a-f]
+//
+// A password must satisfy every clause. Classes can't contain ':',
+// since that ends the rule.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rule {
+    pub clauses: Vec<Clause>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Clause {
+    pub bounds: Bounds,
+    pub needle: Needle,
+}
+
+// The range in a clause. At least one end is always present.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Bounds {
+    pub least: Option<usize>,
+    pub most: Option<usize>,
+}
+
+impl Bounds {
+    // Whether n lies within the bounds, inclusive.
+    pub fn contains(&self, n: usize) -> bool {
+        return self.least.is_none_or(|least| n >= least) && self.most.is_none_or(|most| n <= most);
+    }
+}
+
+// What a clause looks for in a password.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Needle {
+    Character(char),
+    Substring(String),
+    // Any character in one of the inclusive ranges.
+    Class(Vec<(char, char)>),
+}
+
+impl Needle {
+    // How many times the needle appears in the password.
+    // Substrings are counted without overlapping.
+    pub fn count(&self, password: &str) -> usize {
+        return match self {
+            Needle::Character(c) => password.matches(*c).count(),
+            Needle::Substring(s) => password.matches(s.as_str()).count(),
+            Needle::Class(ranges) => password.chars().filter(|&c| in_class(ranges, c)).count()
+        };
+    }
+
+    // Whether the needle appears starting at a position, counting from 1.
+    // Positions outside the password hold nothing.
+    pub fn at(&self, password: &str, position: usize) -> bool {
+        let rest = match position.checked_sub(1).and_then(|i| password.char_indices().nth(i)) {
+            Some((i, _)) => &password[i..],
+            None => return false
+        };
+
+        return match self {
+            Needle::Character(c) => rest.starts_with(*c),
+            Needle::Substring(s) => rest.starts_with(s.as_str()),
+            Needle::Class(ranges) => rest.chars().next().is_some_and(|c| in_class(ranges, c))
+        };
+    }
+}
+
+fn in_class(ranges: &[(char, char)], c: char) -> bool {
+    return ranges.iter().any(|&(low, high)| low <= c && c <= high);
+}
+
+fn parse_rule(rule: &str) -> Result<Rule> {
+    let mut clauses = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let end = clause_end(rule, start);
+        let text = &rule[start..end];
+        let trimmed = text.trim_start();
+        let offset = start + text.len() - trimmed.len();
+
+        if trimmed.is_empty() && end < rule.len() {
+            return Err(Error::parse("Empty clause in rule.").at_column(offset + 1));
+        }
+
+        clauses.push(parse_clause(trimmed, offset)?);
+
+        if end == rule.len() {
+            return Ok(Rule { clauses });
+        }
+
+        start = end + 1;
+    }
+}
+
+// Where the clause starting at <start> ends: at the next ';', or the
+// end of the rule. A ';' inside a class needle doesn't count.
+fn clause_end(rule: &str, start: usize) -> usize {
+    let rest = &rule[start..];
+    let lead = rest.len() - rest.trim_start().len();
+    let mut search = 0;
+
+    // The needle follows the first space, if it comes before any ';'.
+    if let Some(i) = rest[lead..].find([' ', ';']).map(|i| lead + i) {
+        let needle = &rest[i + 1..];
+
+        if rest[i..].starts_with(' ') && needle.starts_with('[') {
+            search = needle.find(']').map_or(rest.len(), |j| i + 1 + j);
+        }
+    }
+
+    return match rest[search..].find(';') {
+        Some(i) => start + search + i,
+        None => rule.len()
+    };
+}
+
+// Parses a single clause, which starts offset bytes into the rule.
+fn parse_clause(clause: &str, offset: usize) -> Result<Clause> {
+    // We expect the clause to have a range and a needle, separated by space.
+    let space_index = match clause.find(' ') {
+        Some(i) => i,
+        None => return Err(Error::parse("Invalid rule.").at_column(offset + 1).with_text(clause))
+    };
+
+    let split = clause.split_at(space_index);
+
+    // Handle the first part of the clause - the range.
+    let range_sep_index = match split.0.find('-') {
+        Some(i) => i,
+        None => return Err(Error::parse("First component of rule is not a range.").at_column(offset + 1).with_text(split.0))
+    };
+
+    // An empty bound is left open.
+    let bound = |text: &str, column: usize| {
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        return match text.parse::<usize>() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => Err(Error::parse(format!("Non-integer in range: {}", text)).at_column(column).with_text(text))
+        };
+    };
+
+    // Split includes the separator in the second half of the string.
+    let split_range = split.0.split_at(range_sep_index);
+    let least = bound(split_range.0, offset + 1)?;
+    let most = bound(&split_range.1[1..], offset + range_sep_index + 2)?;
+
+    if least.is_none() && most.is_none() {
+        return Err(Error::parse("Range must have at least one bound.").at_column(offset + 1).with_text(split.0));
+    }
+
+    // Now get the needle for the clause.
+    // This is the bit after the space-separator.
+    let needle = parse_needle(split.1[1..].trim_end(), offset + space_index + 2)?;
+
+    return Ok(Clause { bounds: Bounds { least, most }, needle });
+}
+
+// Parses a needle starting at the given column.
+fn parse_needle(needle: &str, column: usize) -> Result<Needle> {
+    if needle.is_empty() {
+        let err = Error::parse("Second component of rule must be a character, substring or class.");
+        return Err(err.at_column(column).with_text(needle));
+    }
+
+    if !needle.starts_with('[') {
+        let mut chars = needle.chars();
+        let first = chars.next().unwrap();
+
+        if chars.next().is_none() {
+            return Ok(Needle::Character(first));
+        }
+
+        return Ok(Needle::Substring(String::from(needle)));
+    }
+
+    if needle.len() < 2 || !needle.ends_with(']') {
+        return Err(Error::parse("Character class is not closed.").at_column(column).with_text(needle));
+    }
+
+    // A '-' between two characters makes a range, anywhere else it
+    // stands for itself.
+    let chars: Vec<char> = needle[1..needle.len() - 1].chars().collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            let (low, high) = (chars[i], chars[i + 2]);
+
+            if low > high {
+                let err = Error::parse(format!("Invalid range in character class: {}-{}", low, high));
+                return Err(err.at_column(column).with_text(needle));
+            }
+
+            ranges.push((low, high));
+            i += 3;
+        } else {
+            ranges.push((chars[i], chars[i]));
+            i += 1;
+        }
+    }
+
+    if ranges.is_empty() {
+        return Err(Error::parse("Character class is empty.").at_column(column).with_text(needle));
+    }
+
+    return Ok(Needle::Class(ranges));
+}
+
+// Parses a rule-password pair.
+fn parse_password(s: &str) -> Result<(Rule, String)> {
+    let split_index = match s.find(':') {
+        Some(i) => i,
+        None => return Err(Error::parse("Line must contain a rule and password, separated by ':'."))
+    };
+
+    let split = s.split_at(split_index);
+    let rule_string = split.0;
+    
+    let rule = parse_rule(rule_string)?;
+
+    let password = split.1[1..].trim();
+
+    return Ok((rule, String::from(password)));
+}
+
+// A way of deciding whether a password is valid under its rule.
+pub trait Policy: Send + Sync {
+    fn valid(&self, rule: &Rule, password: &str) -> bool;
+}
+
+// Any function of a rule and a password can be used as a policy.
+impl<F: Fn(&Rule, &str) -> bool + Send + Sync> Policy for F {
+    fn valid(&self, rule: &Rule, password: &str) -> bool {
+        return self(rule, password);
+    }
+}
+
+// The sled rental place's policy: each needle must appear
+// between <least> and <most> times, inclusive.
+pub struct Sled;
+
+impl Policy for Sled {
+    fn valid(&self, rule: &Rule, password: &str) -> bool {
+        return rule.clauses.iter().all(|c| c.bounds.contains(c.needle.count(password)));
+    }
+}
+
+// The toboggan rental place's policy: each needle must be at
+// exactly one of positions <least> and <most>, counting from 1.
+// A missing bound is a position that never matches.
+pub struct Toboggan;
+
+impl Policy for Toboggan {
+    fn valid(&self, rule: &Rule, password: &str) -> bool {
+        return rule.clauses.iter().all(|c| {
+            let at = |position: Option<usize>| position.is_some_and(|p| c.needle.at(password, p));
+            return at(c.bounds.least) != at(c.bounds.most);
+        });
+    }
+}
+
+// Valid only under both policies.
+pub struct And(pub Box<dyn Policy>, pub Box<dyn Policy>);
+
+impl Policy for And {
+    fn valid(&self, rule: &Rule, password: &str) -> bool {
+        return self.0.valid(rule, password) && self.1.valid(rule, password);
+    }
+}
+
+// Valid under either policy.
+pub struct Or(pub Box<dyn Policy>, pub Box<dyn Policy>);
+
+impl Policy for Or {
+    fn valid(&self, rule: &Rule, password: &str) -> bool {
+        return self.0.valid(rule, password) || self.1.valid(rule, password);
+    }
+}
+
+// Valid only if not valid under the policy.
+pub struct Not(pub Box<dyn Policy>);
+
+impl Policy for Not {
+    fn valid(&self, rule: &Rule, password: &str) -> bool {
+        return !self.0.valid(rule, password);
+    }
+}
+
+// The built-in policies, by name.
+const POLICIES: [&str; 2] = ["sled", "toboggan"];
+
+fn named_policy(name: &str) -> Option<Box<dyn Policy>> {
+    return match name {
+        "sled" => Some(Box::new(Sled)),
+        "toboggan" => Some(Box::new(Toboggan)),
+        _ => None
+    };
+}
+
+// Splits a policy into words and symbols, with the column each starts at.
+fn policy_tokens(s: &str) -> Vec<(usize, String)> {
+    let mut tokens: Vec<(usize, String)> = Vec::new();
+    let mut word: Option<(usize, String)> = None;
+
+    for (i, c) in s.char_indices() {
+        if c.is_alphanumeric() {
+            word.get_or_insert_with(|| (i + 1, String::new())).1.push(c.to_ascii_lowercase());
+            continue;
+        }
+
+        tokens.extend(word.take());
+
+        if !c.is_whitespace() {
+            tokens.push((i + 1, c.to_string()));
+        }
+    }
+
+    tokens.extend(word);
+    return tokens;
+}
+
+// Parses a policy made up of the built-in policies, combined with
+// "and", "or" and "not" (or &, | and !) and grouped with brackets.
+// Not binds most tightly, then and, then or. Names are case-insensitive.
+pub fn parse_policy(s: &str) -> Result<Box<dyn Policy>> {
+    let tokens = policy_tokens(s);
+    let mut parser = PolicyParser { tokens: &tokens, next: 0, end: s.len() + 1 };
+
+    let policy = parser.any()?;
+
+    if let Some((column, token)) = parser.peek() {
+        return Err(Error::parse(format!("Unexpected {}", token)).at_column(column).with_text(token));
+    }
+
+    return Ok(policy);
+}
+
+struct PolicyParser<'a> {
+    tokens: &'a [(usize, String)],
+    next: usize,
+
+    // The column just past the end of the policy.
+    end: usize
+}
+
+impl PolicyParser<'_> {
+    fn peek(&self) -> Option<(usize, &str)> {
+        return self.tokens.get(self.next).map(|(column, token)| (*column, token.as_str()));
+    }
+
+    // Moves past the next token if it's one of <options>.
+    fn take(&mut self, options: &[&str]) -> bool {
+        let found = self.peek().is_some_and(|(_, token)| options.contains(&token));
+
+        if found {
+            self.next += 1;
+        }
+
+        return found;
+    }
+
+    // Policies joined by "or".
+    fn any(&mut self) -> Result<Box<dyn Policy>> {
+        let mut policy = self.all()?;
+
+        while self.take(&["or", "|"]) {
+            policy = Box::new(Or(policy, self.all()?));
+        }
+
+        return Ok(policy);
+    }
+
+    // Policies joined by "and".
+    fn all(&mut self) -> Result<Box<dyn Policy>> {
+        let mut policy = self.single()?;
+
+        while self.take(&["and", "&"]) {
+            policy = Box::new(And(policy, self.single()?));
+        }
+
+        return Ok(policy);
+    }
+
+    // A named policy, a negated one, or one in brackets.
+    fn single(&mut self) -> Result<Box<dyn Policy>> {
+        if self.take(&["not", "!"]) {
+            return Ok(Box::new(Not(self.single()?)));
+        }
+
+        if self.take(&["("]) {
+            let policy = self.any()?;
+
+            if !self.take(&[")"]) {
+                let column = self.peek().map_or(self.end, |(column, _)| column);
+                return Err(Error::parse("Expected ')'").at_column(column));
+            }
+
+            return Ok(policy);
+        }
+
+        let (column, name) = match self.peek() {
+            Some(token) => token,
+            None => return Err(Error::parse("Expected a policy").at_column(self.end))
+        };
+
+        return match named_policy(name) {
+            Some(policy) => {
+                self.next += 1;
+                Ok(policy)
+            },
+            None => {
+                let message = format!("Unknown policy: {} (policies are {})", name, POLICIES.join(", "));
+                Err(Error::parse(message).at_column(column).with_text(name))
+            }
+        };
+    }
+}
+
+// Tests a particular password using the policy provided
+// against a given rule, returning whether the password matches or not.
+fn password_valid<P: Policy>(r: &Rule, policy: P, p: &str) -> bool {
+    return policy.valid(r, p);
+}
+
+// Counts the passwords valid under each of several policies,
+// going through the passwords once.
+pub fn count_valid(input: &[(Rule, String)], policies: &[Box<dyn Policy>]) -> Vec<usize> {
+    let mut counts = vec![0; policies.len()];
+
+    for (rule, password) in input {
+        for (count, policy) in counts.iter_mut().zip(policies) {
+            if policy.valid(rule, password) {
+                *count += 1;
+            }
+        }
+    }
+
+    return counts;
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<(Rule, String)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(Rule, String)>> {
+        // Read test data into vector.
+        let mut v: Vec<(Rule, String)> = Vec::new();
+
+        // Iterate over each line.
+        for (i, line) in input.lines().enumerate() {
+            let password = parse_password(line).map_err(|e| e.at_line(i + 1))?;
+            v.push(password);
+        }
+
+        return Ok(v);
+    }
+
+    fn part1(input: &Vec<(Rule, String)>) -> Result<usize> {
+        return Ok(input.iter().filter(|(r, p)| password_valid(r, Sled, p)).count());
+    }
+
+    fn part2(input: &Vec<(Rule, String)>) -> Result<usize> {
+        return Ok(input.iter().filter(|(r, p)| password_valid(r, Toboggan, p)).count());
+    }
+}
@@ -0,0 +1,275 @@
+// Advent of Code 2020
+// Day 3
+
+use std::fmt;
+
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::solution::{Part, Solution};
+use crate::viz::{Frame, Renderer};
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    // The example from part 1 of the puzzle.
+    #[test]
+    fn test_example_part1() {
+        let grid = "
+        ..##.......
+        #...#...#..
+        .#....#..#.
+        ..#.#...#.#
+        .#...##..#.
+        ..#.##.....
+        .#.#.#....#
+        .#........#
+        #.##...#...
+        #...##....#
+        .#..#...#.#";
+
+        assert_eq!(7, trees_in_slope(3, 1, grid).expect("Error calculating number of trees."));
+    }
+
+    // The example from part 2 of the puzzle.
+    #[test]
+    fn test_example_part2() {
+        let grid = "
+        ..##.......
+        #...#...#..
+        .#....#..#.
+        ..#.#...#.#
+        .#...##..#.
+        ..#.##.....
+        .#.#.#....#
+        .#........#
+        #.##...#...
+        #...##....#
+        .#..#...#.#";
+
+        assert_eq!(2, trees_in_slope(1, 1, grid).expect("Error calculating number of trees."));
+        assert_eq!(7, trees_in_slope(3, 1, grid).expect("Error calculating number of trees."));
+        assert_eq!(3, trees_in_slope(5, 1, grid).expect("Error calculating number of trees."));
+        assert_eq!(4, trees_in_slope(7, 1, grid).expect("Error calculating number of trees."));
+        assert_eq!(2, trees_in_slope(1, 2, grid).expect("Error calculating number of trees."));
+    }
+}
+
+#[cfg(test)]
+mod test_visualise {
+    use super::*;
+
+    use crate::viz::Headless;
+
+    #[test]
+    fn path() {
+        let grid = parse_grid("..#.\n.#..\n..##\n#...").unwrap();
+
+        let mut headless = Headless::new();
+        Day3::visualise(&grid, Part::One, &mut headless).expect("Error visualising");
+
+        // One frame per row, with the path wrapping around.
+        assert_eq!(4, headless.frames.len());
+
+        let last = headless.frames.last().unwrap();
+
+        assert_eq!("O.#.\n.#.O\n..X#\n#O..\n", last.to_string());
+        assert_eq!("Right 3, down 1, trees: 1", last.caption());
+        assert_eq!(Some((1, 3)), last.focus());
+    }
+}
+
+#[cfg(test)]
+mod test_grid_parsing {
+    use super::*;
+
+    // Ensure we raise an error when trying to parse an empty grid.
+    #[test]
+    fn test_empty_grid() {
+        let grid = "";
+
+        let err = parse_grid(grid).expect_err("Error not returned");
+
+        assert_eq!("Invalid grid: empty", err.to_string());
+    }
+
+    // Ensure we raise an error when grid lines are of different lengths.
+    #[test]
+    fn test_grid_with_mismatched_lines() {
+        let grid = "..#..#\n...##";
+
+        let err = parse_grid(grid).expect_err("Error not returned");
+
+        assert_eq!("Line 2: Invalid grid: lines of different lengths", err.to_string());
+    }
+
+    // Ensure we raise an error when unsupported characters are used in a grid.
+    #[test]
+    fn test_grid_with_unsupported_chars() {
+        let grid = "..#.\n..#Y";
+
+        let err = parse_grid(grid).expect_err("Error not returned");
+
+        assert_eq!("Line 2, column 4: Invalid grid: unsupported character", err.to_string());
+    }
+
+    // Ensure we can parse a valid grid correctly.
+    #[test]
+    fn test_valid_grid() {
+        let grid_string = "..#.\n.#..\n..##";
+
+        let grid = parse_grid(grid_string).expect("Grid parsing failed");
+
+        assert_eq!(false, tree_at(&grid, 0, 0));
+        assert_eq!(true, tree_at(&grid, 2, 0));
+        assert_eq!(false, tree_at(&grid, 0, 1));
+        assert_eq!(true, tree_at(&grid, 3, 2));
+    }
+
+    // Ensure we can index a grid using modulo arithmetic.
+    #[test]
+    fn test_valid_grid_modulo() {
+        let grid_string = "..#.\n.#..\n..##";
+
+        let grid = parse_grid(grid_string).expect("Grid parsing failed");
+
+        assert_eq!(true, tree_at(&grid, 6, 0));
+        assert_eq!(false, tree_at(&grid, 4, 1));
+        assert_eq!(true, tree_at(&grid, 7, 2));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Square {
+    Open,
+    Tree
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Square::Open => write!(f, "."),
+            Square::Tree => write!(f, "#")
+        }
+    }
+}
+
+// Whether there is a tree at a position. The grid repeats
+// to the right forever, so x can be past its edge.
+fn tree_at(grid: &Grid<Square>, x: usize, y: usize) -> bool {
+    return *grid.get_wrapping(x as isize, y as isize) == Square::Tree;
+}
+
+fn parse_grid(g: &str) -> Result<Grid<Square>> {
+    return Grid::parse(g, |c| {
+        match c {
+            '.' => Some(Square::Open),
+            '#' => Some(Square::Tree),
+            _ => None
+        }
+    });
+}
+
+// Parses a grid and counts the trees encountered on a slope through it,
+// going <right> squares across for every <down> squares down.
+#[cfg(test)]
+fn trees_in_slope(right: usize, down: usize, grid: &str) -> Result<usize> {
+    let grid = parse_grid(grid)?;
+
+    return Ok(count_trees(&grid, right, down));
+}
+
+// Counts the trees encountered on a slope through an already-parsed grid.
+fn count_trees(grid: &Grid<Square>, right: usize, down: usize) -> usize {
+    let mut x = 0;
+    let mut y = 0;
+
+    let mut num_trees = 0;
+
+    while y < grid.height() {
+        if tree_at(grid, x, y) {
+            num_trees += 1;
+        }
+
+        x += right;
+        y += down;
+    }
+
+    return num_trees;
+}
+
+// The slopes checked in part 2, as (right, down).
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+// Draws the toboggan going down a slope, a step at a time, marking
+// each square it stops on: O for open ground, X for a tree.
+// The map repeats to the right, so the path wraps around it.
+fn draw_slope(grid: &Grid<Square>, right: usize, down: usize, renderer: &mut dyn Renderer) -> Result<()> {
+    let mut frame = Frame::from_grid(grid);
+
+    let mut x = 0;
+    let mut y = 0;
+    let mut trees = 0;
+
+    while y < grid.height() {
+        let tree = tree_at(grid, x, y);
+
+        if tree {
+            trees += 1;
+        }
+
+        frame.set(x % grid.width(), y, if tree { 'X' } else { 'O' });
+
+        let caption = format!("Right {}, down {}, trees: {}", right, down, trees);
+        frame = frame.with_focus(x % grid.width(), y).with_caption(caption);
+
+        renderer.render(&frame)?;
+
+        x += right;
+        y += down;
+    }
+
+    return Ok(());
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input = Grid<Square>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(grid: &str) -> Result<Grid<Square>> {
+        return parse_grid(grid);
+    }
+
+    fn part1(grid: &Grid<Square>) -> Result<usize> {
+        return Ok(count_trees(grid, 3, 1));
+    }
+
+    fn part2(grid: &Grid<Square>) -> Result<usize> {
+        let num_trees_a = count_trees(grid, SLOPES[0].0, SLOPES[0].1);
+        let num_trees_b = count_trees(grid, SLOPES[1].0, SLOPES[1].1);
+        let num_trees_c = count_trees(grid, SLOPES[2].0, SLOPES[2].1);
+        let num_trees_d = count_trees(grid, SLOPES[3].0, SLOPES[3].1);
+        let num_trees_e = count_trees(grid, SLOPES[4].0, SLOPES[4].1);
+
+        return Ok(num_trees_a * num_trees_b * num_trees_c * num_trees_d * num_trees_e);
+    }
+
+    // Part 1 goes down one slope, part 2 down each in turn.
+    fn visualise(grid: &Grid<Square>, part: Part, renderer: &mut dyn Renderer) -> Result<()> {
+        let slopes = match part {
+            Part::One => &SLOPES[1..2],
+            Part::Two => &SLOPES[..]
+        };
+
+        for &(right, down) in slopes {
+            draw_slope(grid, right, down, renderer)?;
+        }
+
+        return Ok(());
+    }
+}
@@ -3,7 +3,10 @@
 
 use std::collections::HashMap;
 
-use aoc::file::*;
+use crate::error::Result;
+use crate::file::*;
+use crate::parse;
+use crate::solution::Solution;
 
 #[cfg(test)]
 mod test_examples {
@@ -15,7 +18,7 @@ mod test_examples {
         ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
         byr:1937 iyr:2017 cid:147 hgt:183cm";
 
-        let (valid, _) = passport_valid(passport);
+        let valid = passport_valid(&parse_passport(passport).expect("Invalid passport"));
 
         assert_eq!(true, valid);
     }
@@ -26,7 +29,7 @@ mod test_examples {
         iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
         hcl:#cfa07d byr:1929";
 
-        let (valid, _) = passport_valid(passport);
+        let valid = passport_valid(&parse_passport(passport).expect("Invalid passport"));
 
         assert_eq!(false, valid);
     }
@@ -39,7 +42,7 @@ mod test_examples {
         ecl:brn pid:760753108 byr:1931
         hgt:179cm";
 
-        let (valid, _) = passport_valid(passport);
+        let valid = passport_valid(&parse_passport(passport).expect("Invalid passport"));
 
         assert_eq!(true, valid);
     }
@@ -50,12 +53,45 @@ mod test_examples {
         hcl:#cfa07d eyr:2025 pid:166559648
         iyr:2011 ecl:brn hgt:59in";
 
-        let (valid, _) = passport_valid(passport);
+        let valid = passport_valid(&parse_passport(passport).expect("Invalid passport"));
 
         assert_eq!(false, valid);
     }
 }
 
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn duplicate_key() {
+        let err = parse_passport("ecl:gry pid:860033327\necl:amb").expect_err("No error returned.");
+        assert_eq!("Line 2, column 1: Duplicate key: ecl", err.to_string());
+    }
+
+    #[test]
+    fn missing_separator() {
+        let err = parse_passport("ecl:gry pid860033327").expect_err("No error returned.");
+        assert_eq!("Line 1, column 9: Expected key:value, found pid860033327", err.to_string());
+    }
+
+    // Errors give the line in the whole input, not the passport.
+    #[test]
+    fn error_line_in_input() {
+        let err = Day4::parse("ecl:gry\n\nhcl:#fffffd\npid:1 pid:2\n").expect_err("No error returned.");
+        assert_eq!("Line 4, column 7: Duplicate key: pid", err.to_string());
+    }
+
+    #[test]
+    fn crlf_line_endings() {
+        let passports = Day4::parse("ecl:gry\r\nhcl:#fffffd\r\n\r\npid:1\r\n").expect("Valid input rejected");
+
+        assert_eq!(2, passports.len());
+        assert_eq!("#fffffd", passports[0]["hcl"]);
+        assert_eq!("1", passports[1]["pid"]);
+    }
+}
+
 #[cfg(test)]
 mod test_fields {
     use super::*;
@@ -170,25 +206,6 @@ mod test_fields {
     }
 }
 
-// Regression testing previous puzzle answers to make sure
-// we don't break anything.
-#[cfg(test)]
-mod test_puzzle_answers {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let answer = part1();
-        assert_eq!(216, answer);
-    }
-
-    #[test]
-    fn test_part2() {
-        let answer = part2();
-        assert_eq!(150, answer);
-    }
-}
-
 fn pid_valid(s: &str) -> bool {
     if s.len() != 9 {
         return false;
@@ -205,7 +222,7 @@ fn pid_valid(s: &str) -> bool {
 }
 
 fn ecl_valid(s: &str) -> bool {
-    let valid = vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+    let valid = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
     return valid.contains(&s);
 }
 
@@ -242,9 +259,9 @@ fn hgt_valid(s: &str) -> bool {
     };
 
     if unit == "cm" {
-        return (value >= 150) && (value <= 193);
+        return (150..=193).contains(&value);
     } else if unit == "in" {
-        return (value >= 59) && (value <= 76);
+        return (59..=76).contains(&value);
     } else {
         return false;
     }
@@ -271,9 +288,8 @@ fn yr_valid(s: &str, min: u32, max: u32) -> bool {
     return (year >= min) && (year <= max);
 }
 
-fn passport_valid_check_fields(p: &str) -> bool {
-    let (valid, passport) = passport_valid(p);
-    if !valid {
+fn passport_valid_check_fields(passport: &Passport) -> bool {
+    if !passport_valid(passport) {
         return false;
     }
 
@@ -296,26 +312,14 @@ fn passport_valid_check_fields(p: &str) -> bool {
         pid_valid(pid);
 }
 
-fn passport_valid(p: &str) -> (bool, HashMap<String, String>) {
-    let p = p.trim();
-
-    let mut fields: HashMap<String, String> = HashMap::new();
-
-    // Fields in password are split by whitespace.
-    for field in p.split(char::is_whitespace) {
-        if field.trim().is_empty() {
-            continue;
-        }
-
-        let split_index = field.find(':').expect(&format!("Invalid password field: {}", field));
-        let split = field.split_at(split_index);
+// A passport's fields, by key.
+pub type Passport = HashMap<String, String>;
 
-        if fields.contains_key(split.0) {
-            panic!(format!("Duplicate key {} in passport:\n{}", split.0, p));
-        }
-        fields.insert(String::from(split.0), String::from(&split.1[1..]));
-    }
+fn parse_passport(p: &str) -> Result<Passport> {
+    return parse::record(p, ':');
+}
 
+fn passport_valid(fields: &Passport) -> bool {
     // All fields except "cid" are required.
     let required_fields = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
 
@@ -323,49 +327,61 @@ fn passport_valid(p: &str) -> (bool, HashMap<String, String>) {
     // Password is invalid if it is missing any one of them.
     for f in required_fields {
         if !fields.contains_key(f) {
-            return (false, fields);
+            return false;
         }
     }
 
-    return (true, fields);
+    return true;
 }
 
-fn part1() -> u32 {
-    // Read test data in, iterate over each line.
-    let mut f = GroupedFileReader::open("data/day4.txt").expect("Could not open data/day4.txt");
+pub struct Day4;
 
-    let mut valid_passports = 0;
+impl Solution for Day4 {
+    const DAY: u32 = 4;
 
-    while let FileReadResult::Success(group) = f.next_group() {
-        let (valid, _) = passport_valid(&group);
-        if valid {
-            valid_passports += 1;
-        }
-    }
+    type Input = Vec<Passport>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    valid_passports
-}
+    fn parse(input: &str) -> Result<Vec<Passport>> {
+        // Iterate over each group.
+        let reader = GroupedFileReader::new(input.as_bytes());
 
-fn part2() -> u32 {
-    // Read test data in, iterate over each line.
-    let mut f = GroupedFileReader::open("data/day4.txt").expect("Could not open data/day4.txt");
+        let mut passports = Vec::new();
 
-    let mut valid_passports = 0;
+        for group in reader {
+            let group = group?;
 
-    while let FileReadResult::Success(group) = f.next_group() {
-        let valid = passport_valid_check_fields(&group);
-        if valid {
-            valid_passports += 1;
+            // Positions in the passport are relative to where it starts.
+            let passport = parse_passport(&group.text()).map_err(|e| e.shift_line(group.start_line() - 1))?;
+            passports.push(passport);
         }
+
+        return Ok(passports);
     }
 
-    valid_passports
-}
+    fn part1(passports: &Vec<Passport>) -> Result<u32> {
+        let mut valid_passports = 0;
 
-fn main() {
-    let valid_passports = part1();
-    println!("Part 1: The number of valid passports is: {}", valid_passports);
+        for passport in passports {
+            if passport_valid(passport) {
+                valid_passports += 1;
+            }
+        }
 
-    let valid_passports = part2();
-    println!("Part 2: The number of valid passports (checking fields) is: {}", valid_passports);
+        Ok(valid_passports)
+    }
+
+    fn part2(passports: &Vec<Passport>) -> Result<u32> {
+        let mut valid_passports = 0;
+
+        for passport in passports {
+            let valid = passport_valid_check_fields(passport);
+            if valid {
+                valid_passports += 1;
+            }
+        }
+
+        Ok(valid_passports)
+    }
 }
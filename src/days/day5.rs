@@ -0,0 +1,215 @@
+// Advent of Code 2020
+// Day 5
+
+use std::collections::HashSet;
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[cfg(test)]
+mod test_examples_part1 {
+    use super::*;
+
+    #[test]
+    fn test_example1() {
+        let (row, column) = search_seat("BFFFBBFRRR");
+        assert_eq!(70, row);
+        assert_eq!(7, column);
+    }
+
+    #[test]
+    fn test_example2() {
+        let (row, column) = search_seat("FFFBBBFRRR");
+        assert_eq!(14, row);
+        assert_eq!(7, column);
+    }
+
+    #[test]
+    fn test_example3() {
+        let (row, column) = search_seat("BBFFBBFRLL");
+        assert_eq!(102, row);
+        assert_eq!(4, column);
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn invalid_character() {
+        let err = Day5::parse("BFFFBBFRRR\nFFFBXBFRRR").expect_err("No error returned.");
+        assert_eq!("Line 2, column 5: Invalid character in boarding pass: X", err.to_string());
+    }
+
+    #[test]
+    fn wrong_length() {
+        let err = Day5::parse("BFFFBBFRR").expect_err("No error returned.");
+        assert_eq!("Line 1, column 1: Boarding pass must be 10 characters: BFFFBBFRR", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_search_row {
+    use super::*;
+
+    #[test]
+    fn test_first() {
+        let rows: Vec<u32> = (0..128).collect();
+        let row = search_row(&rows, "FFFFFFF");
+        assert_eq!(0, row);
+    }
+
+    #[test]
+    fn test_last() {
+        let rows: Vec<u32> = (0..128).collect();
+        let row = search_row(&rows, "BBBBBBB");
+        assert_eq!(127, row);
+    }
+
+    #[test]
+    fn test_example() {
+        let rows: Vec<u32> = (0..128).collect();
+        let row = search_row(&rows, "FBFBBFF");
+        assert_eq!(44, row);
+    }
+}
+
+#[cfg(test)]
+mod test_search_column {
+    use super::*;
+
+    #[test]
+    fn test_left() {
+        let columns: Vec<u32> = (0..8).collect();
+        let column = search_column(&columns, "LLL");
+        assert_eq!(0, column);
+    }
+
+    #[test]
+    fn test_right() {
+        let columns: Vec<u32> = (0..8).collect();
+        let column = search_column(&columns, "RRR");
+        assert_eq!(7, column);
+    }
+
+    #[test]
+    fn test_example() {
+        let columns: Vec<u32> = (0..8).collect();
+        let column = search_column(&columns, "RLR");
+        assert_eq!(5, column);
+    }
+}
+
+fn search_column(v: &[u32], pattern: &str) -> u32 {
+    return search(v, pattern, 'L', 'R');
+}
+
+fn search_row(v: &[u32], pattern: &str) -> u32 {
+    return search(v, pattern, 'F', 'B');
+}
+
+fn search(v: &[u32], pattern: &str, first: char, last: char) -> u32 {
+    let direction = pattern.chars().next().unwrap();
+
+    let search_range = if direction == first {
+        &v[..v.len()/2]
+    } else if direction == last {
+        &v[v.len()/2..]
+    } else {
+        panic!("Invalid character in search pattern")
+    };
+
+    if search_range.len() == 1 {
+        return *search_range.first().unwrap();
+    } else {
+        return search(search_range, &pattern[1..], first, last);
+    }
+}
+
+// Checks a boarding pass is 7 row characters followed by
+// 3 column characters, so that it can be searched.
+fn check_boarding_pass(s: &str) -> Result<()> {
+    if s.chars().count() != 10 {
+        return Err(Error::parse(format!("Boarding pass must be 10 characters: {}", s)).at_column(1).with_text(s));
+    }
+
+    for (i, (offset, c)) in s.char_indices().enumerate() {
+        let valid = if i < 7 { c == 'F' || c == 'B' } else { c == 'L' || c == 'R' };
+
+        if !valid {
+            return Err(Error::parse(format!("Invalid character in boarding pass: {}", c)).at_column(offset + 1).with_text(c));
+        }
+    }
+
+    return Ok(());
+}
+
+fn search_seat(s: &str) -> (u32, u32) {
+    let rows: Vec<u32> = (0..128).collect();
+    let row = search_row(&rows, &s[..7]);
+
+    let columns: Vec<u32> = (0..8).collect();
+    let column = search_column(&columns, &s[7..]);
+    (row, column)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    type Input = HashSet<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<HashSet<u32>> {
+        // Hashset to store IDs.
+        let mut ids: HashSet<u32> = HashSet::new();
+
+        // Iterate over each line.
+        for line in parse::lines(input) {
+            check_boarding_pass(line.text).map_err(|e| line.locate(e))?;
+
+            let (row, column) = search_seat(line.text);
+            let id = row * 8 + column;
+
+            ids.insert(id);
+        }
+
+        return Ok(ids);
+    }
+
+    fn part1(ids: &HashSet<u32>) -> Result<u32> {
+        return match ids.iter().max() {
+            Some(id) => Ok(*id),
+            None => Err(Error::NoSolution(String::from("No boarding passes")))
+        };
+    }
+
+    fn part2(ids: &HashSet<u32>) -> Result<u32> {
+        // We need to find the ID of a missing seat that is not at the front
+        // or back of the plane. We know that ID+1 and ID-1 exist, so we can search
+        // the set for a seat with ID X where X+1 doesn't exist but X+2 does.
+
+        let mut possible: Vec<u32> = Vec::new();
+
+        for id in ids {
+            let id = *id;
+            if ids.contains(&(id+1)) {
+                continue;
+            }
+
+            if ids.contains(&(id+2)) {
+                possible.push(id+1);
+            }
+        }
+
+        if possible.len() != 1 {
+            return Err(Error::NoSolution(format!("{} possible missing seat IDs", possible.len())));
+        }
+
+        return Ok(possible[0]);
+    }
+}
@@ -3,7 +3,9 @@
 
 use std::collections::HashSet;
 
-use aoc::file::*;
+use crate::error::{Error, Result};
+use crate::file::*;
+use crate::solution::Solution;
 
 #[cfg(test)]
 mod test_examples_part1 {
@@ -162,38 +164,46 @@ mod test_examples_part2 {
     }
 }
 
-// Regression testing previous puzzle answers to make sure
-// we don't break anything.
 #[cfg(test)]
-mod test_puzzle_answers {
+mod test_parse {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        let answer = part1();
-        assert_eq!(6633, answer);
+    fn crlf_line_endings() {
+        let groups = Day6::parse("ab\r\nac\r\n\r\nb\r\n").expect("Valid input rejected");
+
+        assert_eq!(4, Day6::part1(&groups).unwrap());
+        assert_eq!(2, Day6::part2(&groups).unwrap());
     }
 
+    // Errors give the line in the whole input, not the group.
     #[test]
-    fn test_part2() {
-        let answer = part2();
-        assert_eq!(3202, answer);
+    fn invalid_character() {
+        let err = Day6::parse("abc\n\nab\naB\n").expect_err("No error returned.");
+        assert_eq!("Line 4, column 2: Invalid character B in group", err.to_string());
     }
 }
 
+// Checks that every answer in a group is a question from a to z.
+// The functions below assume this has already been done.
+fn check_group(group: &str) -> Result<()> {
+    for (i, l) in group.lines().enumerate() {
+        for (j, c) in l.char_indices() {
+            if !c.is_ascii_lowercase() && !c.is_whitespace() {
+                let err = Error::parse(format!("Invalid character {} in group", c)).with_text(c);
+                return Err(err.at_line(i + 1).at_column(j + 1));
+            }
+        }
+    }
+
+    return Ok(());
+}
+
 fn group_answers(group: &str) -> HashSet<char> {
     let mut s: HashSet<char> = HashSet::new();
 
     for l in group.trim().lines() {
-        let l = l.trim();
-
-        for c in l.chars() {
-            if ('a'..='z').contains(&c) {
-                s.insert(c);
-            } else {
-                panic!(format!("Invalid character {} in group:\n{}", c, group));
-            }
-        }
+        s.extend(l.trim().chars());
     }
 
     s
@@ -202,54 +212,60 @@ fn group_answers(group: &str) -> HashSet<char> {
 fn group_answers_intersection(group: &str) -> HashSet<char> {
     // Start with the set of all possible answers.
     // We will exclude from this set as we go.
-    let mut answers: HashSet<char> = HashSet::from(('a'..='z').collect());
+    let mut answers: HashSet<char> = ('a'..='z').collect();
 
     for l in group.trim().lines() {
-        let mut s: HashSet<char> = HashSet::new();
-        for c in l.chars() {
-            if ('a'..='z').contains(&c) {
-                s.insert(c);
-            } else {
-                panic!(format!("Invalid character {} in group:\n{}", c, group));
-            }
-        }
+        let s: HashSet<char> = l.trim().chars().collect();
 
-        answers = answers.intersection(&s).map(|c| { *c }).collect::<HashSet<char>>();
+        answers = answers.intersection(&s).copied().collect::<HashSet<char>>();
     }
 
     answers
 }
 
-fn part1() -> usize {
-    let mut f = GroupedFileReader::open("data/day6.txt").expect("Could not open puzzle data.");
+pub struct Day6;
 
-    let mut answers_total = 0;
+impl Solution for Day6 {
+    const DAY: u32 = 6;
 
-    while let FileReadResult::Success(group) = f.next_group() {
-        let answers = group_answers(&group);
-        answers_total += answers.len();
-    }
+    type Input = Vec<Group>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    answers_total
-}
+    fn parse(input: &str) -> Result<Vec<Group>> {
+        let reader = GroupedFileReader::new(input.as_bytes());
+
+        let mut groups = Vec::new();
 
-fn part2() -> usize {
-    let mut f = GroupedFileReader::open("data/day6.txt").expect("Could not open puzzle data.");
+        for group in reader {
+            let group = group?;
+            check_group(&group.text()).map_err(|e| e.shift_line(group.start_line() - 1))?;
 
-    let mut answers_total = 0;
+            groups.push(group);
+        }
 
-    while let FileReadResult::Success(group) = f.next_group() {
-        let answers = group_answers_intersection(&group);
-        answers_total += answers.len();
+        return Ok(groups);
     }
 
-    answers_total
-}
+    fn part1(groups: &Vec<Group>) -> Result<usize> {
+        let mut answers_total = 0;
+
+        for group in groups {
+            let answers = group_answers(&group.text());
+            answers_total += answers.len();
+        }
 
-fn main() {
-    let answer_part1 = part1();
-    println!("Part 1: The sum of all positive answers is: {}", answer_part1);
+        Ok(answers_total)
+    }
+
+    fn part2(groups: &Vec<Group>) -> Result<usize> {
+        let mut answers_total = 0;
+
+        for group in groups {
+            let answers = group_answers_intersection(&group.text());
+            answers_total += answers.len();
+        }
 
-    let answer_part2 = part2();
-    println!("Part 2: The sum of the size of the intersection of each group's answers is: {}", answer_part2);
-}
\ No newline at end of file
+        Ok(answers_total)
+    }
+}
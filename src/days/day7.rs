@@ -4,7 +4,9 @@
 use std::collections::{HashMap, HashSet};
 use std::str::Split;
 
-use std::fs;
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
 
 // Test the examples from the puzzle to a single depth,
 // i.e. test that we can correctly parse a single rule and identify what a given bag
@@ -18,7 +20,7 @@ mod test_examples_single_depth {
         let rules = "
         light red bags contain 1 bright white bag, 2 muted yellow bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let light_red = Bag::from_str("light red");
 
@@ -36,7 +38,7 @@ mod test_examples_single_depth {
         let rules = "
         dark orange bags contain 3 bright white bags, 4 muted yellow bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let light_red = Bag::from_str("dark orange");
 
@@ -54,7 +56,7 @@ mod test_examples_single_depth {
         let rules = "
         bright white bags contain 1 shiny gold bag.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let bright_white = Bag::from_str("bright white");
 
@@ -70,7 +72,7 @@ mod test_examples_single_depth {
         let rules = "
         muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let muted_yellow = Bag::from_str("muted yellow");
 
@@ -88,7 +90,7 @@ mod test_examples_single_depth {
         let rules = "
         shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let shiny_gold = Bag::from_str("shiny gold");
 
@@ -106,7 +108,7 @@ mod test_examples_single_depth {
         let rules = "
         dark olive bags contain 3 faded blue bags, 4 dotted black bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let dark_olive = Bag::from_str("dark olive");
 
@@ -124,7 +126,7 @@ mod test_examples_single_depth {
         let rules = "
         vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let vibrant_plum = Bag::from_str("vibrant plum");
 
@@ -142,7 +144,7 @@ mod test_examples_single_depth {
         let rules = "
         faded blue bags contain no other bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let faded_blue = Bag::from_str("faded blue");
 
@@ -156,7 +158,7 @@ mod test_examples_single_depth {
         let rules = "
         dotted black bags contain no other bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let dotted_black = Bag::from_str("dotted black");
 
@@ -173,7 +175,7 @@ mod test_count {
     #[test]
     fn test_contains_none() {
         let rules = "bright pink bags contain no other bags.";
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let bright_pink = Bag::from_str("bright pink");
 
@@ -187,7 +189,7 @@ mod test_count {
         dark green bags contain 1 bright pink bag, 4 deep purple bags.
         deep purple bags contain no other bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let dark_green = Bag::from_str("dark green");
 
@@ -202,7 +204,7 @@ mod test_count {
         deep purple bags contain 5 lovely lilac bags.
         lovely lilac bags contain no other bags.";
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(rules).expect("Invalid rules");
 
         let dark_green = Bag::from_str("dark green");
 
@@ -214,22 +216,15 @@ mod test_count {
 mod test_example {
     use super::*;
 
+    use crate::examples;
+
     // Tests that we can collect all the types of bags
     // that exist in a ruleset.
     #[test]
     fn example_collect() {
-        let rules = "
-        light red bags contain 1 bright white bag, 2 muted yellow bags.
-        dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-        bright white bags contain 1 shiny gold bag.
-        muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-        shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-        dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-        vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-        faded blue bags contain no other bags.
-        dotted black bags contain no other bags.";
+        let rules = examples::read(7, "example1.txt").expect("Example not found");
 
-        let ruleset = Ruleset::from_str(rules);
+        let ruleset = Ruleset::from_str(&rules).expect("Invalid rules");
         let bags = ruleset.bags();
 
         assert_eq!(true, bags.contains(&Bag::from_str("light red")));
@@ -250,18 +245,9 @@ mod test_example {
     // Test the complete example from part 1, positive cases.
     #[test]
     fn example_part1_positive() {
-        let rules = "
-        light red bags contain 1 bright white bag, 2 muted yellow bags.
-        dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-        bright white bags contain 1 shiny gold bag.
-        muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-        shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-        dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-        vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-        faded blue bags contain no other bags.
-        dotted black bags contain no other bags.";
+        let rules = examples::read(7, "example1.txt").expect("Example not found");
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(&rules).expect("Invalid rules");
 
         let shiny_gold = Bag::from_str("shiny gold");
 
@@ -281,18 +267,9 @@ mod test_example {
     // Test the complete example from part 1, positive cases.
     #[test]
     fn example_part1_negative() {
-        let rules = "
-        light red bags contain 1 bright white bag, 2 muted yellow bags.
-        dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-        bright white bags contain 1 shiny gold bag.
-        muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-        shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-        dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-        vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-        faded blue bags contain no other bags.
-        dotted black bags contain no other bags.";
+        let rules = examples::read(7, "example1.txt").expect("Example not found");
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(&rules).expect("Invalid rules");
 
         let shiny_gold = Bag::from_str("shiny gold");
 
@@ -314,18 +291,9 @@ mod test_example {
     // Test the first example from part 2
     #[test]
     fn example_part2_a() {
-        let rules = "
-        light red bags contain 1 bright white bag, 2 muted yellow bags.
-        dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-        bright white bags contain 1 shiny gold bag.
-        muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-        shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-        dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-        vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-        faded blue bags contain no other bags.
-        dotted black bags contain no other bags.";
+        let rules = examples::read(7, "example1.txt").expect("Example not found");
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(&rules).expect("Invalid rules");
 
         let shiny_gold = Bag::from_str("shiny gold");
         
@@ -335,16 +303,9 @@ mod test_example {
     // Test the second example from part 2
     #[test]
     fn example_part2_b() {
-        let rules = "
-        shiny gold bags contain 2 dark red bags.
-        dark red bags contain 2 dark orange bags.
-        dark orange bags contain 2 dark yellow bags.
-        dark yellow bags contain 2 dark green bags.
-        dark green bags contain 2 dark blue bags.
-        dark blue bags contain 2 dark violet bags.
-        dark violet bags contain no other bags.";
+        let rules = examples::read(7, "example2.txt").expect("Example not found");
 
-        let rules = Ruleset::from_str(rules);
+        let rules = Ruleset::from_str(&rules).expect("Invalid rules");
 
         let shiny_gold = Bag::from_str("shiny gold");
         
@@ -365,110 +326,178 @@ mod test_bag {
     #[test]
     fn test_from_iter() {
         let colour = "bright red";
-        let bag = Bag::from_iter(&mut colour.split(" "));
+        let bag = Bag::from_iter(&mut colour.split(" ")).expect("Invalid bag");
         assert_eq!("bright red", bag.colour());
     }
+
+    #[test]
+    fn test_from_iter_too_short() {
+        let err = Bag::from_iter(&mut "bright".split(" ")).err().expect("No error returned.");
+        assert_eq!("Rule ends unexpectedly.", err.to_string());
+    }
 }
 
 #[cfg(test)]
-mod test_puzzles {
+mod test_errors {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        assert_eq!(208, part1());
+    fn invalid_rule() {
+        let rules = "faded blue bags contain no other bags.\ndotted black bags hold no other bags.";
+
+        let err = Ruleset::from_str(rules).err().expect("No error returned.");
+        assert_eq!("Line 2, column 19: Invalid rule: expected \"contain\", found \"hold\"", err.to_string());
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(1664, part2());
+    fn invalid_number() {
+        let rules = "light red bags contain one bright white bag.";
+
+        let err = Ruleset::from_str(rules).err().expect("No error returned.");
+        assert_eq!("Line 1, column 24: Invalid number of bags: one", err.to_string());
+    }
+
+    // Columns are byte offsets, which is where the caret is drawn.
+    #[test]
+    fn invalid_number_non_ascii() {
+        let rules = "lïght réd bags contain x shiny gold bags.";
+
+        let err = Ruleset::from_str(rules).err().expect("No error returned.");
+        assert_eq!("Line 1, column 26: Invalid number of bags: x", err.to_string());
+    }
+
+    #[test]
+    fn rule_ends_unexpectedly() {
+        let rules = "\n  light red bags contain 1 bright";
+
+        let err = Ruleset::from_str(rules).err().expect("No error returned.");
+        assert_eq!("Line 2, column 34: Rule ends unexpectedly.", err.to_string());
+    }
+
+    #[test]
+    fn undefined_bag() {
+        let rules = "light red bags contain 1 bright white bag.";
+
+        let err = Day7::parse(rules).err().expect("No error returned.");
+        assert_eq!("Invalid puzzle state: Bag bright white not defined in this ruleset", err.to_string());
     }
 }
 
-struct Ruleset {
+pub struct Ruleset {
     ruleset: HashMap<Bag, HashMap<Bag, u32>>
 }
 
 impl Ruleset {
-    fn from_str(rules: &str) -> Ruleset {
+    fn from_str(rules: &str) -> Result<Ruleset> {
         let mut ruleset: HashMap<Bag, HashMap<Bag, u32>> = HashMap::new();
 
-        for rule in rules.split('\n') {
-            let rule = rule.trim();
+        for line in parse::lines(rules) {
+            // A rule that ends too soon is missing something at the end.
+            let end = line.text.len() + 1;
 
-            // Skip blank lines.
-            if rule.is_empty() {
-                continue;
-            }
+            let (containing_bag, contains) = Ruleset::parse_rule(line.text)
+                .map_err(|e| line.locate(e.at_column(end)))?;
 
-            let mut rule = rule.split(" ");
+            ruleset.insert(containing_bag, contains);
+        }
 
-            let containing_bag = Bag::from_iter(&mut rule);
+        return Ok(Ruleset {
+            ruleset
+        });
+    }
 
-            // Now we should have "bags contain"
-            if rule.next().unwrap() != "bags" {
-                panic!("Invalid rule.");
-            }
+    // Parses a single rule into the bag it is for
+    // and the bags that bag contains.
+    fn parse_rule(rule: &str) -> Result<(Bag, HashMap<Bag, u32>)> {
+        let line = rule;
+        let mut rule = rule.split(" ");
 
-            if rule.next().unwrap() != "contain" {
-                panic!("Invalid rule.");
+        // An error for a word other than the one(s) expected.
+        let unexpected = |word: &str, expected: &str| {
+            let message = format!("Invalid rule: expected {}, found \"{}\"", expected, word);
+            return Error::parse(message).at_column(parse::column_of(line, word)).with_text(word);
+        };
+
+        let containing_bag = Bag::from_iter(&mut rule)?;
+
+        // Now we should have "bags contain"
+        for expected in &["bags", "contain"] {
+            let word = next_word(&mut rule)?;
+
+            if word != *expected {
+                return Err(unexpected(word, &format!("\"{}\"", expected)));
             }
+        }
 
-            // Now loop over the remaining part of the rule,
-            // to get all the kinds of bag that this bag can contain.
-            let mut contains: HashMap<Bag, u32> = HashMap::new();
+        // Now loop over the remaining part of the rule,
+        // to get all the kinds of bag that this bag can contain.
+        let mut contains: HashMap<Bag, u32> = HashMap::new();
 
-            loop {
-                // First, a number.
-                let number = rule.next().unwrap();
+        loop {
+            // First, a number.
+            let number = next_word(&mut rule)?;
 
-                // Is this actually a number? It could also be the word "no"
-                if number == "no" {
-                    if rule.next().unwrap() != "other" {
-                        panic!("Invalid rule.");
-                    }
+            // Is this actually a number? It could also be the word "no"
+            if number == "no" {
+                for expected in &["other", "bags."] {
+                    let word = next_word(&mut rule)?;
 
-                    if rule.next().unwrap() != "bags." {
-                        panic!("Invalid rule.");
+                    if word != *expected {
+                        return Err(unexpected(word, &format!("\"{}\"", expected)));
                     }
+                }
+
+                break;
+            }
 
-                    break;
+            // Otherwise it _should_ be a number.
+            let number = match number.parse() {
+                Ok(n) => n,
+                Err(_) => {
+                    let err = Error::parse(format!("Invalid number of bags: {}", number));
+                    return Err(err.at_column(parse::column_of(line, number)).with_text(number));
                 }
+            };
+
+            // Now, a bag.
+            let bag = Bag::from_iter(&mut rule)?;
 
-                // Otherwise it _should_ be a number.
-                // If it's not, this will panic.
-                let number = number.parse().unwrap();
+            // Put the bag in the bag.
+            contains.insert(bag, number);
 
-                // Now, a bag.
-                let bag = Bag::from_iter(&mut rule);
 
-                // Put the bag in the bag.
-                contains.insert(bag, number);
+            // Now "bag." or "bags." if this is the last bag,
+            // or "bag," or "bags," if not.
+            // Anything else is an error.
+            match next_word(&mut rule)? {
+                "bag." | "bags." => break,
+                "bag," | "bags," => continue,
+                word => return Err(unexpected(word, "\"bags.\" or \"bags,\""))
+            }
+        }
 
+        return Ok((containing_bag, contains));
+    }
 
-                // Now "bag." or "bags." if this is the last bag,
-                // or "bag," or "bags," if not.
-                // For anything else we panic.
-                match rule.next().unwrap() {
-                    "bag." | "bags." => break,
-                    "bag," | "bags," => continue,
-                    _ => panic!("Invalid rule")
+    // Checks that every bag contained by another bag
+    // has a rule of its own.
+    fn check(&self) -> Result<()> {
+        for contains in self.ruleset.values() {
+            for bag in contains.keys() {
+                if !self.ruleset.contains_key(bag) {
+                    return Err(Error::InvalidState(format!("Bag {} not defined in this ruleset", bag.colour())));
                 }
             }
-
-            ruleset.insert(containing_bag, contains);
         }
 
-        return Ruleset {
-            ruleset
-        };
+        return Ok(());
     }
 
     // Returns all the bag colours defined under this ruleset.
     fn bags(&self) -> HashSet<&Bag> {
         let mut s: HashSet<&Bag> = HashSet::new();
 
-        for (bag, _) in &self.ruleset {
+        for bag in self.ruleset.keys() {
             s.insert(bag);
         }
 
@@ -478,7 +507,7 @@ impl Ruleset {
     // Returns the types and number of bags that can be contained
     // in the given bag, with this ruleset.
     fn contains(&self, bag: &Bag) -> &HashMap<Bag, u32> {
-        return self.ruleset.get(bag).expect(&format!("Bag {} not defined in this ruleset", bag.colour()));
+        return self.ruleset.get(bag).unwrap_or_else(|| panic!("Bag {} not defined in this ruleset", bag.colour()));
     }
 }
 
@@ -494,13 +523,13 @@ impl Bag {
         }
     }
 
-    fn from_iter(s: &mut Split<&str>) -> Bag {
+    fn from_iter(s: &mut Split<&str>) -> Result<Bag> {
         let mut b = String::new();
-        b.push_str(s.next().unwrap());
+        b.push_str(next_word(s)?);
         b.push(' ');
-        b.push_str(s.next().unwrap());
+        b.push_str(next_word(s)?);
 
-        return Bag::from_str(&b);
+        return Ok(Bag::from_str(&b));
     }
 
     fn colour(&self) -> &str {
@@ -516,12 +545,12 @@ impl Bag {
         // Recursive case: At least one direct child.
         //     Return the union of the sets that each direct child
         //     contains.
-        if direct_children.len() == 0 {
+        if direct_children.is_empty() {
             return false;
         } else if direct_children.contains_key(other) {
             return true;
         } else {
-            for (child_bag, _) in direct_children {
+            for child_bag in direct_children.keys() {
                 if child_bag.contains(rules, other) {
                     return true
                 }
@@ -548,40 +577,55 @@ impl Bag {
     }
 }
 
-fn part1() -> u32 {
-    // Read rules into file.
-    let rules = fs::read_to_string("data/day7.txt").unwrap();
-    let rules = Ruleset::from_str(&rules);
+// The next word of a rule, which it is an error to be missing.
+fn next_word<'a>(s: &mut Split<'a, &str>) -> Result<&'a str> {
+    return match s.next() {
+        Some(word) => Ok(word),
+        None => Err(Error::parse("Rule ends unexpectedly."))
+    };
+}
+
+pub struct Day7;
 
-    // Collect all the types of bags.
-    let bags = rules.bags();
+impl Solution for Day7 {
+    const DAY: u32 = 7;
 
-    // We're looking for bags that contain "shiny gold".
-    let search_bag = Bag::from_str("shiny gold");
-    let mut count = 0;
+    type Input = Ruleset;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    for bag in bags {
-        if bag.contains(&rules, &search_bag) {
-            count += 1;
-        }
+    fn parse(rules: &str) -> Result<Ruleset> {
+        let rules = Ruleset::from_str(rules)?;
+        rules.check()?;
+
+        return Ok(rules);
     }
 
-    return count;
-}
+    fn part1(rules: &Ruleset) -> Result<u32> {
+        // Collect all the types of bags.
+        let bags = rules.bags();
 
-fn part2() -> u32 {
-    // Read rules into file.
-    let rules = fs::read_to_string("data/day7.txt").unwrap();
-    let rules = Ruleset::from_str(&rules);
+        // We're looking for bags that contain "shiny gold".
+        let search_bag = Bag::from_str("shiny gold");
+        let mut count = 0;
 
-    // How many bags fit inside a "shiny gold" bag?
-    return Bag::from_str("shiny gold").count(&rules);
-}
+        for bag in bags {
+            if bag.contains(rules, &search_bag) {
+                count += 1;
+            }
+        }
+
+        return Ok(count);
+    }
 
-fn main() {
-    let count_part1 = part1();
-    println!("Part 1: Number of bags that contain shiny gold is: {}", count_part1);
+    fn part2(rules: &Ruleset) -> Result<u32> {
+        let shiny_gold = Bag::from_str("shiny gold");
+
+        if !rules.bags().contains(&shiny_gold) {
+            return Err(Error::NoSolution(String::from("No rule for shiny gold bags")));
+        }
 
-    let count_part2 = part2();
-    println!("Part 2: Number of bags that fit inside a shiny gold bag is: {}", count_part2);
+        // How many bags fit inside a "shiny gold" bag?
+        return Ok(shiny_gold.count(rules));
+    }
 }
@@ -0,0 +1,534 @@
+// Advent of Code 2020
+// Day 8
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::debug::Simulation;
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::{Part, Solution};
+
+#[cfg(test)]
+mod test_parse_instructon {
+    use super::*;
+
+    #[test]
+    fn nop() {
+        assert_eq!(Instruction::Nop(0), Instruction::parse("nop +0").unwrap());
+    }
+
+    #[test]
+    fn acc() {
+        assert_eq!(Instruction::Acc(1), Instruction::parse("acc +1").unwrap());
+        assert_eq!(Instruction::Acc(3), Instruction::parse("acc +3").unwrap());
+        assert_eq!(Instruction::Acc(-99), Instruction::parse("acc -99").unwrap());
+        assert_eq!(Instruction::Acc(0), Instruction::parse("acc +0").unwrap());
+    }
+
+    #[test]
+    fn jmp() {
+        assert_eq!(Instruction::Jmp(4), Instruction::parse("jmp +4").unwrap());
+        assert_eq!(Instruction::Jmp(-3), Instruction::parse("jmp -3").unwrap());
+        assert_eq!(Instruction::Jmp(0), Instruction::parse("jmp +0").unwrap());
+    }
+
+    #[test]
+    fn unknown_opcode() {
+        let err = Instruction::parse("xyz +1").expect_err("No error returned.");
+        assert_eq!("Column 1: Unknown opcode: xyz", err.to_string());
+    }
+
+    #[test]
+    fn invalid_operand() {
+        let err = Day8::parse("nop +0\nacc one").expect_err("No error returned.");
+        assert_eq!("Line 2, column 5: Operand is not a valid integer: one", err.to_string());
+    }
+
+    // Errors give the line and column in the file, counting
+    // blank lines and indentation.
+    #[test]
+    fn error_position() {
+        let err = Day8::parse("\n\nnop +0\n  acc one").expect_err("No error returned.");
+        assert_eq!("Line 4, column 7: Operand is not a valid integer: one", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_interpret {
+    use super::*;
+
+    #[test]
+    fn nop1() {
+        let state = ProgramState { instruction: 0, accumulator: 0 };
+        let state = execute(&Instruction::Nop(0), &state);
+        assert_eq!(1, state.instruction);
+        assert_eq!(0, state.accumulator);
+    }
+
+    #[test]
+    fn nop2() {
+        let state = ProgramState { instruction: 4, accumulator: 5 };
+        let state = execute(&Instruction::Nop(0), &state);
+        assert_eq!(5, state.instruction);
+        assert_eq!(5, state.accumulator);
+    }
+
+    #[test]
+    fn acc1() {
+        let state = ProgramState { instruction: 4, accumulator: 7 };
+        let state = execute(&Instruction::Acc(5), &state);
+        assert_eq!(5, state.instruction);
+        assert_eq!(12, state.accumulator);
+    }
+
+    #[test]
+    fn acc2() {
+        let state = ProgramState { instruction: 6, accumulator: 7 };
+        let state = execute(&Instruction::Acc(-3), &state);
+        assert_eq!(7, state.instruction);
+        assert_eq!(4, state.accumulator);
+    }
+
+    #[test]
+    fn acc3() {
+        let state = ProgramState { instruction: 5, accumulator: 7 };
+        let state = execute(&Instruction::Acc(-9), &state);
+        assert_eq!(6, state.instruction);
+        assert_eq!(-2, state.accumulator);
+    }
+
+    #[test]
+    fn jmp1() {
+        let state = ProgramState { instruction: 4, accumulator: 7 };
+        let state = execute(&Instruction::Jmp(2), &state);
+        assert_eq!(6, state.instruction);
+        assert_eq!(7, state.accumulator);
+    }
+
+    #[test]
+    fn jmp2() {
+        let state = ProgramState { instruction: 4, accumulator: 4 };
+        let state = execute(&Instruction::Jmp(-3), &state);
+        assert_eq!(1, state.instruction);
+        assert_eq!(4, state.accumulator);
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    #[test]
+    fn test_example_part1() {
+        let program = vec![
+            Instruction::Nop(0),
+            Instruction::Acc(1),
+            Instruction::Jmp(4),
+            Instruction::Acc(3),
+            Instruction::Jmp(-3),
+            Instruction::Acc(-99),
+            Instruction::Acc(1),
+            Instruction::Jmp(-4),
+            Instruction::Acc(6),
+        ];
+
+        let state = find_loop(&program).expect("Program failed").0;
+
+        assert_eq!(1, state.instruction);
+        assert_eq!(5, state.accumulator);
+    }
+
+    #[test]
+    fn test_example_part2_noloop() {
+        let program = vec![
+            Instruction::Nop(0),
+            Instruction::Acc(1),
+            Instruction::Jmp(4),
+            Instruction::Acc(3),
+            Instruction::Jmp(-3),
+            Instruction::Acc(-99),
+            Instruction::Acc(1),
+            Instruction::Nop(-4),
+            Instruction::Acc(6),
+        ];
+
+        let state = find_loop(&program).expect("Program failed").0;
+
+        assert_eq!(program.len(), state.instruction as usize);
+        assert_eq!(8, state.accumulator);
+    }
+
+    #[test]
+    fn test_example_part2_break_loop() {
+        let program = vec![
+            Instruction::Nop(0),
+            Instruction::Acc(1),
+            Instruction::Jmp(4),
+            Instruction::Acc(3),
+            Instruction::Jmp(-3),
+            Instruction::Acc(-99),
+            Instruction::Acc(1),
+            Instruction::Jmp(-4),
+            Instruction::Acc(6),
+        ];
+
+        let new_program = break_loop(&program).expect("Could not break loop");
+
+        // New program must be same size as old one.
+        assert_eq!(program.len(), new_program.len());
+
+        // Second to last instruction should be changed
+        // to a nop.
+        let expected_program = vec![
+            Instruction::Nop(0),
+            Instruction::Acc(1),
+            Instruction::Jmp(4),
+            Instruction::Acc(3),
+            Instruction::Jmp(-3),
+            Instruction::Acc(-99),
+            Instruction::Acc(1),
+            Instruction::Nop(-4),
+            Instruction::Acc(6),
+        ];
+
+        assert_eq!(expected_program, new_program);
+    }
+}
+
+#[cfg(test)]
+mod test_simulate {
+    use super::*;
+
+    const EXAMPLE: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    #[test]
+    fn steps_to_loop() {
+        let program = Day8::parse(EXAMPLE).unwrap();
+        let mut execution = Day8::simulate(&program, Part::One).unwrap();
+
+        assert_eq!("instruction 0 (nop +0), accumulator 0", execution.summary());
+
+        execution.step();
+        execution.step();
+        assert_eq!("instruction 2 (jmp +4), accumulator 1", execution.summary());
+
+        while !execution.finished() {
+            execution.step();
+        }
+
+        // Stops before running instruction 1 a second time.
+        assert_eq!(7, execution.steps());
+        assert_eq!("instruction 1 (acc +1), accumulator 5, looping", execution.summary());
+        assert_eq!(vec![("instruction", 1), ("acc", 5), ("visited", 7)], execution.variables());
+    }
+
+    // Part 2 steps through the fixed program, to the end.
+    #[test]
+    fn steps_to_end() {
+        let program = Day8::parse(EXAMPLE).unwrap();
+        let mut execution = Day8::simulate(&program, Part::Two).unwrap();
+
+        while !execution.finished() {
+            execution.step();
+        }
+
+        assert_eq!("instruction 9 (end of program), accumulator 8", execution.summary());
+    }
+
+    #[test]
+    fn state() {
+        let program = Day8::parse(EXAMPLE).unwrap();
+        let mut execution = Day8::simulate(&program, Part::One).unwrap();
+
+        for _ in 0..4 {
+            execution.step();
+        }
+
+        let expected = "ProgramState { instruction: 7, accumulator: 2 }
+Visited 4 of 9 instructions
+
+     4: jmp -3
+     5: acc -99
+   * 6: acc +1
+ >   7: jmp -4
+     8: acc +6
+";
+
+        assert_eq!(expected, execution.state());
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ProgramState {
+    instruction: i32,
+    accumulator: i32
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Instruction {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32)
+}
+
+impl Instruction {
+    fn parse(s: &str) -> Result<Instruction> {
+        let s = s.trim();
+        let mut s_split = s.trim().split(' ');
+        
+        let opcode = match s_split.next() {
+            Some(o) if !o.is_empty() => o,
+            _ => return Err(Error::parse(format!("No opcode in instruction: '{}'", s)))
+        };
+
+        let operand = match s_split.next() {
+            Some(o) => o,
+            None => return Err(Error::parse(format!("No operand in instruction: '{}'", s)))
+        };
+
+        let operand: i32 = match operand.parse() {
+            Ok(n) => n,
+            Err(_) => {
+                let err = Error::parse(format!("Operand is not a valid integer: {}", operand));
+                return Err(err.at_column(opcode.len() + 2).with_text(operand));
+            }
+        };
+
+        return match opcode {
+            "nop" => Ok(Instruction::Nop(operand)),
+            "acc" => Ok(Instruction::Acc(operand)),
+            "jmp" => Ok(Instruction::Jmp(operand)),
+            _ => Err(Error::parse(format!("Unknown opcode: {}", opcode)).at_column(1).with_text(opcode))
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Nop(n) => write!(f, "nop {:+}", n),
+            Instruction::Acc(n) => write!(f, "acc {:+}", n),
+            Instruction::Jmp(n) => write!(f, "jmp {:+}", n)
+        }
+    }
+}
+
+fn execute(i: &Instruction, s: &ProgramState) -> ProgramState {
+    let instruction = s.instruction;
+    let accumulator = s.accumulator;
+
+    return match i {
+        Instruction::Nop(_) => ProgramState { instruction: instruction + 1, accumulator },
+        Instruction::Acc(n) => ProgramState { instruction: instruction + 1, accumulator: accumulator + n },
+        Instruction::Jmp(n) => ProgramState { instruction: instruction + n, accumulator }
+    };
+}
+
+// Returns the program state and set of visited instructions
+// either on termination or on the second visit to any given instruction.
+//
+// Jumping anywhere other than to an instruction
+// or just past the end of the program is an error.
+fn find_loop(p: &[Instruction]) -> Result<(ProgramState, HashSet<i32>)> {
+    let mut state = ProgramState { instruction: 0, accumulator: 0 };
+
+    let mut visited: HashSet<i32> = HashSet::new();
+
+    loop {
+        let index = state.instruction as usize;
+
+        let instruction = match p.get(index) {
+            Some(i) => i,
+            None => return Err(Error::InvalidState(format!("Execution out of bounds: {}", state.instruction)))
+        };
+
+        state = execute(instruction, &state);
+
+        if (state.instruction as usize) == p.len() {
+            return Ok((state, visited));
+        }
+
+        if visited.contains(&state.instruction) {
+            return Ok((state, visited));
+        }
+
+        visited.insert(state.instruction);
+    }
+}
+
+// Given a program with an infinite loop, returns an altered
+// version of that program with one instruction changed,
+// which does not contain a loop.
+fn break_loop(p: &[Instruction]) -> Result<Vec<Instruction>> {
+    // Get the instructions visited by the original program.
+    let visited = find_loop(p)?.1;
+
+    // For each nop or jmp in the visited instructions,
+    // change it and see if the program completes.
+    for i in visited {
+        let new_instruction = match p.get(i as usize).unwrap() {
+            Instruction::Nop(n) => Instruction::Jmp(*n),
+            Instruction::Jmp(n) => Instruction::Nop(*n),
+            Instruction::Acc(_) => continue // Skip adds
+        };
+
+        // Clone the program.
+        let mut new_p = p.to_vec();
+
+        // Insert the new instruction.
+        new_p[i as usize] = new_instruction;
+
+        // Run, see if it loops.
+        // A change which makes the program jump out of bounds
+        // doesn't fix it either.
+        let state = match find_loop(&new_p) {
+            Ok((state, _)) => state,
+            Err(_) => continue
+        };
+
+        let index = state.instruction as usize;
+        if index == new_p.len() {
+            return Ok(new_p);
+        }
+    }
+
+    return Err(Error::NoSolution(String::from("Could not break loop")));
+}
+
+// Runs a program an instruction at a time, until it ends or is about
+// to run an instruction for the second time.
+struct Execution {
+    program: Vec<Instruction>,
+    state: ProgramState,
+    visited: HashSet<i32>,
+    steps: usize
+}
+
+impl Execution {
+    fn new(program: Vec<Instruction>) -> Execution {
+        return Execution {
+            program,
+            state: ProgramState { instruction: 0, accumulator: 0 },
+            visited: HashSet::new(),
+            steps: 0
+        };
+    }
+
+    // The instruction about to be run, if it's in the program.
+    fn next(&self) -> Option<&Instruction> {
+        if self.state.instruction < 0 {
+            return None;
+        }
+
+        return self.program.get(self.state.instruction as usize);
+    }
+}
+
+// Instructions shown either side of the next one when showing the state.
+const LISTING_CONTEXT: i32 = 3;
+
+impl Simulation for Execution {
+    fn step(&mut self) {
+        let next = self.next().unwrap().clone();
+
+        self.visited.insert(self.state.instruction);
+        self.state = execute(&next, &self.state);
+        self.steps += 1;
+    }
+
+    fn steps(&self) -> usize {
+        return self.steps;
+    }
+
+    fn finished(&self) -> bool {
+        return self.next().is_none() || self.visited.contains(&self.state.instruction);
+    }
+
+    fn summary(&self) -> String {
+        let next = match self.next() {
+            Some(i) => i.to_string(),
+            None if self.state.instruction == self.program.len() as i32 => String::from("end of program"),
+            None => String::from("out of bounds")
+        };
+
+        let mut summary = format!("instruction {} ({}), accumulator {}", self.state.instruction, next, self.state.accumulator);
+
+        if self.visited.contains(&self.state.instruction) {
+            summary.push_str(", looping");
+        }
+
+        return summary;
+    }
+
+    // The program state, and a listing of the program around the next
+    // instruction (>), marking those already run (*).
+    fn state(&self) -> String {
+        let mut state = format!("{:?}\nVisited {} of {} instructions\n\n", self.state, self.visited.len(), self.program.len());
+
+        let first = (self.state.instruction - LISTING_CONTEXT).max(0);
+        let last = (self.state.instruction + LISTING_CONTEXT).min(self.program.len() as i32 - 1);
+        let width = last.to_string().len();
+
+        for i in first..=last {
+            let next = if i == self.state.instruction { '>' } else { ' ' };
+            let visited = if self.visited.contains(&i) { '*' } else { ' ' };
+
+            state.push_str(&format!(" {} {} {:>width$}: {}\n", next, visited, i, self.program[i as usize], width = width));
+        }
+
+        return state;
+    }
+
+    fn variables(&self) -> Vec<(&'static str, i64)> {
+        return vec![
+            ("instruction", self.state.instruction as i64),
+            ("acc", self.state.accumulator as i64),
+            ("visited", self.visited.len() as i64)
+        ];
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(s: &str) -> Result<Vec<Instruction>> {
+        let mut p: Vec<Instruction> = Vec::new();
+
+        for line in parse::lines(s) {
+            p.push(Instruction::parse(line.text).map_err(|e| line.locate(e))?);
+        }
+
+        return Ok(p);
+    }
+
+    fn part1(p: &Vec<Instruction>) -> Result<i32> {
+        let state = find_loop(p)?.0;
+
+        return Ok(state.accumulator);
+    }
+
+    fn part2(p: &Vec<Instruction>) -> Result<i32> {
+        let fixed_p = break_loop(p)?;
+        let state = find_loop(&fixed_p)?.0;
+
+        return Ok(state.accumulator);
+    }
+
+    // Part 1 steps through the program as it is, part 2 through
+    // the program with its loop broken.
+    fn simulate(p: &Vec<Instruction>, part: Part) -> Result<Box<dyn Simulation>> {
+        let program = match part {
+            Part::One => p.clone(),
+            Part::Two => break_loop(p)?
+        };
+
+        return Ok(Box::new(Execution::new(program)));
+    }
+}
@@ -2,13 +2,15 @@
 // Day 9
 
 use std::collections::HashSet;
-use std::iter::{FromIterator};
 
-use std::fs;
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
 
 #[cfg(test)]
 mod test_examples {
     use super::*;
+    use std::iter::FromIterator;
 
     #[test]
     fn test_example_part1() {
@@ -40,7 +42,7 @@ mod test_examples {
     
         let input: Vec<u64> = Vec::from_iter(input);
 
-        let invalid = first_invalid(&input, 5);
+        let invalid = first_invalid(&input, 5).expect("No invalid value found");
         assert_eq!(127, invalid);
     }
 
@@ -74,7 +76,7 @@ mod test_examples {
     
         let input: Vec<u64> = Vec::from_iter(input);
 
-        let (min, max) = find_weakness(&input, 127);
+        let (min, max) = find_weakness(&input, 127).expect("No weakness found");
         assert_eq!(15, min);
         assert_eq!(47, max);
     }
@@ -115,21 +117,6 @@ mod test_is_valid {
     }
 }
 
-#[cfg(test)]
-mod test_puzzle {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(85848519, part1());
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(13414198, part2());
-    }
-}
-
 fn is_valid(values: &[u64], i: usize, preamble_count: usize) -> bool {
     // Make sure that i is in range:
     // - is >= the preamble count
@@ -175,7 +162,7 @@ fn is_valid(values: &[u64], i: usize, preamble_count: usize) -> bool {
     return false;
 }
 
-fn find_weakness(input: &[u64], invalid: u64) -> (u64, u64) {
+fn find_weakness(input: &[u64], invalid: u64) -> Result<(u64, u64)> {
     // Vector to hold our contiguous set that sum to invalid.
     let mut s: Vec<u64> = Vec::new();
 
@@ -200,7 +187,7 @@ fn find_weakness(input: &[u64], invalid: u64) -> (u64, u64) {
             let sum = s.iter().sum::<u64>();
 
             if sum == invalid {
-                return (*s.iter().min().unwrap(), *s.iter().max().unwrap());
+                return Ok((*s.iter().min().unwrap(), *s.iter().max().unwrap()));
             } else if sum > invalid {
                 s.clear();
                 break;
@@ -208,52 +195,40 @@ fn find_weakness(input: &[u64], invalid: u64) -> (u64, u64) {
         }
     }
 
-    panic!("Could not find contiguous set!");
+    return Err(Error::NoSolution(String::from("Could not find contiguous set")));
 }
 
-fn first_invalid(input: &[u64], preamble_count: usize) -> u64 {
+fn first_invalid(input: &[u64], preamble_count: usize) -> Result<u64> {
     for i in preamble_count..input.len() {
-        if !is_valid(&input, i, preamble_count) {
-            return input[i];
+        if !is_valid(input, i, preamble_count) {
+            return Ok(input[i]);
         }
     }
 
-    panic!("Could not find invalid value!");
+    return Err(Error::NoSolution(String::from("Could not find invalid value")));
 }
 
-fn part1() -> u64 {
-    let input = fs::read_to_string("data/day9.txt")
-        .expect("Could not read data/day9.txt");
+pub struct Day9;
 
-    let input = input.trim().split('\n').map(|i| {
-        i.trim().parse().unwrap()
-    });
+impl Solution for Day9 {
+    const DAY: u32 = 9;
 
-    let input: Vec<u64> = Vec::from_iter(input);
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    return first_invalid(&input, 25);
-}
-
-fn part2() -> u64 {
-    let input = fs::read_to_string("data/day9.txt")
-        .expect("Could not read data/day9.txt");
-
-    let input = input.trim().split('\n').map(|i| {
-        i.trim().parse().unwrap()
-    });
-
-    let input: Vec<u64> = Vec::from_iter(input);
-
-    let invalid = first_invalid(&input, 25);
+    fn parse(input: &str) -> Result<Vec<u64>> {
+        return parse::numbers(input);
+    }
 
-    let (min, max) = find_weakness(&input, invalid);
-    return min + max;
-}
+    fn part1(input: &Vec<u64>) -> Result<u64> {
+        return first_invalid(input, 25);
+    }
 
-fn main() {
-    let invalid_part1 = part1();
-    println!("Part 1: The first invalid number in the list is: {}", invalid_part1);
+    fn part2(input: &Vec<u64>) -> Result<u64> {
+        let invalid = first_invalid(input, 25)?;
 
-    let weakness_part2 = part2();
-    println!("Part 2: The weakness is: {}", weakness_part2);
+        let (min, max) = find_weakness(input, invalid)?;
+        return Ok(min + max);
+    }
 }
@@ -0,0 +1,43 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+
+use crate::solution::{puzzle, Puzzle};
+
+// Every day solved so far, in order.
+pub fn all() -> Vec<Box<dyn Puzzle>> {
+    return vec![
+        puzzle::<day1::Day1>(),
+        puzzle::<day2::Day2>(),
+        puzzle::<day3::Day3>(),
+        puzzle::<day4::Day4>(),
+        puzzle::<day5::Day5>(),
+        puzzle::<day6::Day6>(),
+        puzzle::<day7::Day7>(),
+        puzzle::<day8::Day8>(),
+        puzzle::<day9::Day9>(),
+        puzzle::<day10::Day10>(),
+        puzzle::<day11::Day11>(),
+        puzzle::<day12::Day12>(),
+        puzzle::<day13::Day13>(),
+        puzzle::<day14::Day14>(),
+        puzzle::<day15::Day15>(),
+    ];
+}
+
+// Returns the puzzle for the given day, if it has been solved.
+pub fn get(day: u32) -> Option<Box<dyn Puzzle>> {
+    return all().into_iter().find(|p| p.day() == day);
+}
@@ -0,0 +1,436 @@
+// Stepping through a simulation interactively, one tick at a time.
+//
+// Days whose solutions run a simulation can hand it over as a
+// Simulation, which the Debugger drives from commands read a line at a
+// time: stepping, jumping ahead, running until a condition holds, and
+// showing the current state.
+
+use std::io::{self, BufRead, Write};
+
+#[cfg(test)]
+mod test_commands {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Ok(Command::Step), Command::parse(""));
+        assert_eq!(Ok(Command::Step), Command::parse("s"));
+        assert_eq!(Ok(Command::Jump(10)), Command::parse("jump 10"));
+        assert_eq!(Ok(Command::Jump(4)), Command::parse("step 4"));
+        assert_eq!(Ok(Command::Run(None)), Command::parse("r"));
+        assert_eq!(Ok(Command::Show), Command::parse("show"));
+        assert_eq!(Ok(Command::Quit), Command::parse("  q  "));
+
+        let condition = Condition { name: String::from("acc"), comparison: Comparison::AtLeast, value: -3 };
+        assert_eq!(Ok(Command::Run(Some(condition))), Command::parse("run acc >= -3"));
+    }
+
+    #[test]
+    fn conditions_without_spaces() {
+        let condition = Condition { name: String::from("spoken"), comparison: Comparison::Equal, value: 0 };
+        assert_eq!(Ok(Command::Run(Some(condition))), Command::parse("run spoken==0"));
+
+        let condition = Condition { name: String::from("turn"), comparison: Comparison::Less, value: 5 };
+        assert_eq!(Ok(Command::Run(Some(condition))), Command::parse("run turn<5"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Err(String::from("Unknown command: fly")), Command::parse("fly"));
+        assert_eq!(Err(String::from("Invalid number of steps: x")), Command::parse("jump x"));
+        assert_eq!(Err(String::from("jump requires a number of steps.")), Command::parse("j"));
+        assert_eq!(Err(String::from("Invalid condition: acc 5")), Command::parse("run acc 5"));
+        assert_eq!(Err(String::from("Invalid condition: acc > five")), Command::parse("run acc > five"));
+    }
+}
+
+#[cfg(test)]
+mod test_debugger {
+    use super::*;
+
+    // Counts up by three each step, finishing at 30.
+    struct Counter {
+        value: i64,
+        steps: usize
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.value += 3;
+            self.steps += 1;
+        }
+
+        fn steps(&self) -> usize {
+            return self.steps;
+        }
+
+        fn finished(&self) -> bool {
+            return self.value >= 30;
+        }
+
+        fn summary(&self) -> String {
+            return format!("Value {}", self.value);
+        }
+
+        fn state(&self) -> String {
+            return format!("Value: {}\nSteps: {}", self.value, self.steps);
+        }
+
+        fn variables(&self) -> Vec<(&'static str, i64)> {
+            return vec![("value", self.value)];
+        }
+    }
+
+    // Runs the debugger on a script of commands, returning whether
+    // it asked to go on and everything it printed.
+    fn debug(script: &str) -> (Counter, bool, String) {
+        let mut counter = Counter { value: 0, steps: 0 };
+        let mut out = Vec::new();
+
+        let more = Debugger::new(script.as_bytes(), &mut out).prompt(false).run(&mut counter).unwrap();
+
+        return (counter, more, String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn step_and_jump() {
+        let (counter, more, out) = debug("step\n\njump 3\n");
+
+        assert_eq!(5, counter.steps);
+        assert_eq!(true, more);
+        assert_eq!("Step 1: Value 3\nStep 2: Value 6\nStep 5: Value 15\n", out);
+    }
+
+    #[test]
+    fn run_to_condition() {
+        let (counter, _, out) = debug("run value > 10\nrun step == 7\n");
+
+        assert_eq!(7, counter.steps);
+        assert_eq!("Step 4: Value 12\nStep 7: Value 21\n", out);
+    }
+
+    // The condition is only checked after each step, so running
+    // always moves on, even if the condition already holds.
+    #[test]
+    fn run_moves_on() {
+        let (counter, _, _) = debug("run value >= 0\n");
+        assert_eq!(1, counter.steps);
+    }
+
+    #[test]
+    fn run_to_end() {
+        let (counter, more, out) = debug("run\nstep\n");
+
+        assert_eq!(10, counter.steps);
+        assert_eq!(true, more);
+        assert_eq!("Step 10: Value 30 (finished)\nAlready finished.\n", out);
+    }
+
+    #[test]
+    fn condition_never_met() {
+        let (_, _, out) = debug("run value < 0\n");
+        assert_eq!("Step 10: Value 30 (finished, condition not met)\n", out);
+    }
+
+    // Jumping no steps shows the current state without moving on.
+    #[test]
+    fn jump_zero() {
+        let (counter, _, out) = debug("step\njump 0\nstep 0\n");
+
+        assert_eq!(1, counter.steps);
+        assert_eq!("Step 1: Value 3\nStep 1: Value 3\nStep 1: Value 3\n", out);
+    }
+
+    #[test]
+    fn jump_past_end() {
+        let (counter, _, _) = debug("jump 100\n");
+        assert_eq!(10, counter.steps);
+    }
+
+    #[test]
+    fn show() {
+        let (_, _, out) = debug("j 2\nshow\n");
+        assert_eq!("Step 2: Value 6\nValue: 6\nSteps: 2\n", out);
+    }
+
+    #[test]
+    fn errors() {
+        let (counter, _, out) = debug("fly\nrun speed > 1\nstep\n");
+
+        assert_eq!(1, counter.steps);
+        assert!(out.starts_with("Unknown command: fly\nUnknown variable: speed (variables are step, value)\n"));
+    }
+
+    #[test]
+    fn quit() {
+        let (counter, more, _) = debug("step\nquit\nstep\n");
+
+        assert_eq!(1, counter.steps);
+        assert_eq!(false, more);
+    }
+
+    #[test]
+    fn prompt() {
+        let mut counter = Counter { value: 0, steps: 0 };
+        let mut out = Vec::new();
+
+        Debugger::new("s\n".as_bytes(), &mut out).run(&mut counter).unwrap();
+
+        assert_eq!("(step 0) Step 1: Value 3\n(step 1) ", String::from_utf8(out).unwrap());
+    }
+}
+
+// A simulation which moves on one tick at a time.
+pub trait Simulation {
+    // Moves on one tick. Never called once finished.
+    fn step(&mut self);
+
+    // Number of ticks so far.
+    fn steps(&self) -> usize;
+
+    // Whether there is nothing more to simulate.
+    fn finished(&self) -> bool;
+
+    // The most important parts of the current state, on one line.
+    fn summary(&self) -> String;
+
+    // All of the current state worth showing, over as many lines as needed.
+    fn state(&self) -> String;
+
+    // Named numbers from the current state, for conditions to check.
+    fn variables(&self) -> Vec<(&'static str, i64)>;
+}
+
+// How a condition compares a variable with a value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    AtMost,
+    Greater,
+    AtLeast
+}
+
+// Operators, longest first so that <= isn't taken for <.
+const OPERATORS: [(&str, Comparison); 6] = [
+    ("==", Comparison::Equal),
+    ("!=", Comparison::NotEqual),
+    ("<=", Comparison::AtMost),
+    (">=", Comparison::AtLeast),
+    ("<", Comparison::Less),
+    (">", Comparison::Greater)
+];
+
+// A condition on one of a simulation's variables, e.g. "acc > 100".
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Condition {
+    name: String,
+    comparison: Comparison,
+    value: i64
+}
+
+impl Condition {
+    fn parse(s: &str) -> Result<Condition, String> {
+        let invalid = || format!("Invalid condition: {}", s);
+
+        let (at, operator, comparison) = OPERATORS.iter()
+            .find_map(|&(operator, comparison)| s.find(operator).map(|at| (at, operator, comparison)))
+            .ok_or_else(invalid)?;
+
+        let name = s[..at].trim();
+        let value = s[at + operator.len()..].trim().parse().map_err(|_| invalid())?;
+
+        if name.is_empty() {
+            return Err(invalid());
+        }
+
+        return Ok(Condition { name: name.to_string(), comparison, value });
+    }
+
+    // Whether the condition holds for a simulation, or an error
+    // if the simulation has no such variable.
+    fn holds(&self, simulation: &dyn Simulation) -> Result<bool, String> {
+        let variables = variables(simulation);
+
+        let actual = match variables.iter().find(|(name, _)| *name == self.name) {
+            Some(&(_, value)) => value,
+            None => {
+                let names: Vec<&str> = variables.iter().map(|(name, _)| *name).collect();
+                return Err(format!("Unknown variable: {} (variables are {})", self.name, names.join(", ")));
+            }
+        };
+
+        return Ok(match self.comparison {
+            Comparison::Equal => actual == self.value,
+            Comparison::NotEqual => actual != self.value,
+            Comparison::Less => actual < self.value,
+            Comparison::AtMost => actual <= self.value,
+            Comparison::Greater => actual > self.value,
+            Comparison::AtLeast => actual >= self.value
+        });
+    }
+}
+
+// A simulation's variables, along with the number of steps taken.
+fn variables(simulation: &dyn Simulation) -> Vec<(&'static str, i64)> {
+    let mut variables = vec![("step", simulation.steps() as i64)];
+    variables.extend(simulation.variables());
+
+    return variables;
+}
+
+// Something the debugger can be told to do.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Command {
+    // Move on one tick.
+    Step,
+
+    // Move on a number of ticks.
+    Jump(usize),
+
+    // Move on until a condition holds, or to the end.
+    Run(Option<Condition>),
+
+    // Print the whole of the current state.
+    Show,
+
+    Help,
+    Quit
+}
+
+impl Command {
+    // Reads a command. An empty line steps, and every command
+    // can be shortened to its first letter.
+    pub fn parse(line: &str) -> Result<Command, String> {
+        let line = line.trim();
+
+        let (word, rest) = match line.find(char::is_whitespace) {
+            Some(at) => (&line[..at], line[at..].trim()),
+            None => (line, "")
+        };
+
+        return match word {
+            "" | "s" | "step" if rest.is_empty() => Ok(Command::Step),
+            "s" | "step" | "j" | "jump" => {
+                if rest.is_empty() {
+                    return Err(String::from("jump requires a number of steps."));
+                }
+
+                match rest.parse() {
+                    Ok(n) => Ok(Command::Jump(n)),
+                    Err(_) => Err(format!("Invalid number of steps: {}", rest))
+                }
+            },
+            "r" | "run" if rest.is_empty() => Ok(Command::Run(None)),
+            "r" | "run" => Ok(Command::Run(Some(Condition::parse(rest)?))),
+            "p" | "show" => Ok(Command::Show),
+            "h" | "help" => Ok(Command::Help),
+            "q" | "quit" => Ok(Command::Quit),
+            _ => Err(format!("Unknown command: {}", line))
+        };
+    }
+}
+
+const HELP: &str = "Commands:
+  step, s, or an empty line     move on one step
+  jump <n>, j <n>, step <n>     move on <n> steps
+  run [<condition>], r          move on until the condition holds, or to the end
+  show, p                       show the current state
+  help, h                       show this help
+  quit, q                       stop debugging
+
+Conditions compare a variable with a number, e.g. 'run acc > 100', using
+==, !=, <, <=, > or >=. Every simulation has a 'step' variable; run with
+an unknown variable to see the rest.";
+
+// Reads commands a line at a time and carries them out on a simulation,
+// printing where it got to after each one.
+pub struct Debugger<R: BufRead, W: Write> {
+    input: R,
+    out: W,
+    prompt: bool
+}
+
+impl<R: BufRead, W: Write> Debugger<R, W> {
+    pub fn new(input: R, out: W) -> Debugger<R, W> {
+        return Debugger { input, out, prompt: true };
+    }
+
+    // Whether to print a prompt before reading each command.
+    pub fn prompt(mut self, prompt: bool) -> Debugger<R, W> {
+        self.prompt = prompt;
+        return self;
+    }
+
+    // Debugs a simulation until there are no more commands, returning
+    // false if told to quit, or true if the input just ran out.
+    pub fn run(&mut self, simulation: &mut dyn Simulation) -> io::Result<bool> {
+        loop {
+            if self.prompt {
+                write!(self.out, "(step {}) ", simulation.steps())?;
+                self.out.flush()?;
+            }
+
+            let mut line = String::new();
+
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(true);
+            }
+
+            let command = match Command::parse(&line) {
+                Ok(c) => c,
+                Err(e) => {
+                    writeln!(self.out, "{}", e)?;
+                    continue;
+                }
+            };
+
+            match command {
+                Command::Quit => return Ok(false),
+                Command::Help => writeln!(self.out, "{}", HELP)?,
+                Command::Show => writeln!(self.out, "{}", simulation.state().trim_end())?,
+                Command::Step => self.advance(simulation, Some(1), None)?,
+                Command::Jump(n) => self.advance(simulation, Some(n), None)?,
+                Command::Run(condition) => self.advance(simulation, None, condition.as_ref())?
+            }
+        }
+    }
+
+    // Steps until <steps> steps have been taken, the condition holds, or
+    // the simulation finishes, then prints where it got to.
+    fn advance(&mut self, simulation: &mut dyn Simulation, steps: Option<usize>, condition: Option<&Condition>) -> io::Result<()> {
+        if simulation.finished() {
+            return writeln!(self.out, "Already finished.");
+        }
+
+        // Check the condition makes sense before running anything.
+        if let Some(Err(e)) = condition.map(|c| c.holds(simulation)) {
+            return writeln!(self.out, "{}", e);
+        }
+
+        let target = steps.map(|n| simulation.steps() + n);
+        let mut met = false;
+
+        // The target is checked before stepping, so jumping
+        // 0 steps just shows where the simulation is.
+        while !simulation.finished() && target.is_none_or(|t| simulation.steps() < t) {
+            simulation.step();
+
+            if let Some(condition) = condition {
+                if condition.holds(simulation) == Ok(true) {
+                    met = true;
+                    break;
+                }
+            }
+        }
+
+        let mut line = format!("Step {}: {}", simulation.steps(), simulation.summary());
+
+        if simulation.finished() {
+            line.push_str(if condition.is_some() && !met { " (finished, condition not met)" } else { " (finished)" });
+        }
+
+        return writeln!(self.out, "{}", line);
+    }
+}
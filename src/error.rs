@@ -0,0 +1,356 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+#[cfg(test)]
+mod test_error {
+    use super::*;
+
+    #[test]
+    fn parse_without_position() {
+        assert_eq!("Invalid rule.", Error::parse("Invalid rule.").to_string());
+    }
+
+    #[test]
+    fn parse_with_position() {
+        let err = Error::parse("Unknown opcode: xyz").at_column(1).at_line(12);
+        assert_eq!("Line 12, column 1: Unknown opcode: xyz", err.to_string());
+
+        let err = Error::parse("Missing bus schedule").at_line(2);
+        assert_eq!("Line 2: Missing bus schedule", err.to_string());
+    }
+
+    // A position already given by an inner parser isn't overwritten
+    // by an outer one.
+    #[test]
+    fn position_set_once() {
+        let err = Error::parse("Bad").at_line(3).at_line(7).at_column(2).at_column(5);
+
+        match err {
+            Error::Parse { line, column, .. } => {
+                assert_eq!(Some(3), line);
+                assert_eq!(Some(2), column);
+            },
+            _ => panic!("Not a parse error")
+        }
+    }
+
+    #[test]
+    fn shift_column() {
+        let err = Error::parse("Bad").at_column(2).shift_column(4);
+        assert_eq!("Column 6: Bad", err.to_string());
+
+        // Nothing to shift if the column isn't known.
+        assert_eq!("Bad", Error::parse("Bad").shift_column(4).to_string());
+    }
+
+    #[test]
+    fn shift_line() {
+        let err = Error::parse("Bad").at_line(2).shift_line(5);
+        assert_eq!("Line 7: Bad", err.to_string());
+
+        assert_eq!("Bad", Error::parse("Bad").shift_line(5).to_string());
+    }
+
+    #[test]
+    fn in_file() {
+        let err = Error::parse("Bad").at_line(2).in_file("data/day7.txt");
+        assert_eq!("data/day7.txt: Line 2: Bad", err.to_string());
+    }
+
+    // Positions only apply to parse errors.
+    #[test]
+    fn position_ignored_for_other_errors() {
+        let err = Error::NoSolution(String::from("Could not break loop")).at_line(3);
+        assert_eq!("No solution: Could not break loop", err.to_string());
+    }
+
+    #[test]
+    fn render_caret() {
+        let input = "F10\nR45\nN3\n";
+        let err = Error::parse("Invalid value for R: 45").at_line(2).at_column(2).with_text("45").in_file("data/day12.txt");
+
+        assert_eq!("\
+error: Invalid value for R: 45
+ --> data/day12.txt:2:2
+  |
+2 | R45
+  |  ^^
+", err.render(input));
+    }
+
+    // Without the offending text, only the first character is marked.
+    #[test]
+    fn render_without_text() {
+        let input = "\t12\n";
+        let err = Error::parse("Bad").at_line(1).at_column(3);
+
+        assert_eq!("\
+error: Bad
+ --> <input>:1:3
+  |
+1 | \t12
+  | \t ^
+", err.render(input));
+    }
+
+    // Columns are byte offsets, however many bytes the characters
+    // before them take up.
+    #[test]
+    fn render_non_ascii() {
+        let input = "2-4 é: ééx\n";
+        let err = Error::parse("Bad").at_line(1).at_column(13).with_text("x");
+
+        assert_eq!("\
+error: Bad
+ --> <input>:1:13
+  |
+1 | 2-4 é: ééx
+  |          ^
+", err.render(input));
+    }
+
+    // A column of 0 is treated as the start of the line.
+    #[test]
+    fn render_column_zero() {
+        let err = Error::parse("Bad").at_line(1).at_column(0);
+        assert_eq!("error: Bad\n --> <input>:1:0\n  |\n1 | abc\n  | ^\n", err.render("abc"));
+    }
+
+    #[test]
+    fn render_without_column() {
+        let input = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let err = Error::parse("Bad").at_line(10).in_file("in.txt");
+
+        assert_eq!("\
+error: Bad
+  --> in.txt:10
+   |
+10 | j
+", err.render(input));
+    }
+
+    #[test]
+    fn render_without_position() {
+        assert_eq!("error: Bad\n", Error::parse("Bad").render(""));
+        assert_eq!("error: Bad\n --> in.txt\n", Error::parse("Bad").in_file("in.txt").render(""));
+
+        let err = Error::NoSolution(String::from("Could not break loop"));
+        assert_eq!("error: No solution: Could not break loop\n", err.render(""));
+    }
+
+    #[test]
+    fn from_io() {
+        let err: Error = io::Error::new(io::ErrorKind::NotFound, "data/day1.txt").into();
+        assert_eq!("I/O error: data/day1.txt", err.to_string());
+    }
+}
+
+// Everything that can go wrong reading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    // The puzzle input could not be read.
+    Io(io::Error),
+
+    // The puzzle input is malformed.
+    //
+    // Lines and columns count from 1, and columns count bytes,
+    // as found by str::find() and the like. Either may be unknown,
+    // e.g. when the error comes from parsing a single line
+    // before the caller has said which line it was.
+    //
+    // The file and the offending text are only used to show where
+    // the error is; see render().
+    Parse {
+        file: Option<String>,
+        line: Option<usize>,
+        column: Option<usize>,
+        text: Option<String>,
+        message: String
+    },
+
+    // The input parsed, but describes something the puzzle
+    // doesn't allow, e.g. a program jumping out of bounds.
+    InvalidState(String),
+
+    // The search for an answer finished without finding one.
+    NoSolution(String)
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    // A parse error whose position isn't known yet.
+    pub fn parse<S: Into<String>>(message: S) -> Error {
+        return Error::Parse { file: None, line: None, column: None, text: None, message: message.into() };
+    }
+
+    // Sets the line of a parse error, unless it is already known.
+    // Other errors are returned unchanged.
+    pub fn at_line(self, n: usize) -> Error {
+        return match self {
+            Error::Parse { file, line, column, text, message } => {
+                Error::Parse { file, line: line.or(Some(n)), column, text, message }
+            },
+            e => e
+        };
+    }
+
+    // Sets the column of a parse error, unless it is already known.
+    // Other errors are returned unchanged.
+    pub fn at_column(self, n: usize) -> Error {
+        return match self {
+            Error::Parse { file, line, column, text, message } => {
+                Error::Parse { file, line, column: column.or(Some(n)), text, message }
+            },
+            e => e
+        };
+    }
+
+    // Moves the column of a parse error right by the given amount,
+    // for errors found by parsing part of a line.
+    pub fn shift_column(self, by: usize) -> Error {
+        return match self {
+            Error::Parse { file, line, column, text, message } => {
+                Error::Parse { file, line, column: column.map(|c| c + by), text, message }
+            },
+            e => e
+        };
+    }
+
+    // Sets the offending text of a parse error, starting at its column,
+    // unless it is already known. Other errors are returned unchanged.
+    pub fn with_text<S: Into<String>>(self, t: S) -> Error {
+        return match self {
+            Error::Parse { file, line, column, text, message } => {
+                Error::Parse { file, line, column, text: text.or_else(|| Some(t.into())), message }
+            },
+            e => e
+        };
+    }
+
+    // Sets the name of the file a parse error was found in,
+    // unless it is already known. Other errors are returned unchanged.
+    pub fn in_file<S: Into<String>>(self, name: S) -> Error {
+        return match self {
+            Error::Parse { file, line, column, text, message } => {
+                Error::Parse { file: file.or_else(|| Some(name.into())), line, column, text, message }
+            },
+            e => e
+        };
+    }
+
+    // Renders the error the way a compiler would, showing the
+    // line of input it was found on with the offending text
+    // (or just its first character, if the text isn't known)
+    // underlined:
+    //
+    //     error: Invalid value for R: 45
+    //      --> data/day12.txt:3:2
+    //       |
+    //     3 | R45
+    //       |  ^^
+    //
+    // <input> is the whole input the error was found in.
+    pub fn render(&self, input: &str) -> String {
+        let (file, line, column, text, message) = match self {
+            Error::Parse { file, line, column, text, message } => (file, line, column, text, message),
+            e => return format!("error: {}\n", e)
+        };
+
+        let mut rendered = format!("error: {}\n", message);
+
+        let location = match (file, line, column) {
+            (f, Some(l), Some(c)) => format!("{}:{}:{}", f.as_deref().unwrap_or("<input>"), l, c),
+            (f, Some(l), None) => format!("{}:{}", f.as_deref().unwrap_or("<input>"), l),
+            (Some(f), None, _) => f.clone(),
+            (None, None, _) => return rendered
+        };
+
+        let line = match line {
+            Some(l) => *l,
+            None => {
+                rendered.push_str(&format!(" --> {}\n", location));
+                return rendered;
+            }
+        };
+
+        let source = match input.lines().nth(line.wrapping_sub(1)) {
+            Some(s) => s.trim_end_matches('\r'),
+            None => {
+                rendered.push_str(&format!(" --> {}\n", location));
+                return rendered;
+            }
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+
+        rendered.push_str(&format!("{}--> {}\n", gutter, location));
+        rendered.push_str(&format!("{} |\n", gutter));
+        rendered.push_str(&format!("{} | {}\n", line, source));
+
+        if let Some(column) = column {
+            // Columns count bytes, so skip every character starting
+            // before the column. Tabs are kept so that the caret lines
+            // up however wide the terminal shows them.
+            let indent: String = source.char_indices()
+                .take_while(|&(i, _)| i < column.saturating_sub(1))
+                .map(|(_, c)| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let width = text.as_ref().map_or(1, |t| t.chars().count().max(1));
+
+            rendered.push_str(&format!("{} | {}{}\n", gutter, indent, "^".repeat(width)));
+        }
+
+        return rendered;
+    }
+
+    // Moves the line of a parse error down by the given amount,
+    // for errors found by parsing part of the input.
+    pub fn shift_line(self, by: usize) -> Error {
+        return match self {
+            Error::Parse { file, line, column, text, message } => {
+                Error::Parse { file, line: line.map(|l| l + by), column, text, message }
+            },
+            e => e
+        };
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::Parse { file, line, column, message, .. } => {
+                if let Some(file) = file {
+                    write!(f, "{}: ", file)?;
+                }
+
+                match (line, column) {
+                    (Some(l), Some(c)) => write!(f, "Line {}, column {}: {}", l, c, message),
+                    (Some(l), None) => write!(f, "Line {}: {}", l, message),
+                    (None, Some(c)) => write!(f, "Column {}: {}", c, message),
+                    (None, None) => write!(f, "{}", message)
+                }
+            },
+            Error::InvalidState(message) => write!(f, "Invalid puzzle state: {}", message),
+            Error::NoSolution(message) => write!(f, "No solution: {}", message)
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return match self {
+            Error::Io(e) => Some(e),
+            _ => None
+        };
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        return Error::Io(e);
+    }
+}
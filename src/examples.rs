@@ -0,0 +1,184 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::input::Source;
+
+// Directory the puzzle examples are kept in, relative to the crate root.
+pub const EXAMPLES_DIR: &str = "examples";
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    use crate::days;
+    use crate::solution::Part;
+    use crate::verify::{self, Status};
+
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn discover_in_order() {
+        let examples = discover(Path::new(EXAMPLES_DIR)).unwrap();
+
+        let day10: Vec<String> = examples.iter()
+            .filter(|e| e.day == 10)
+            .map(|e| e.name())
+            .collect();
+
+        assert_eq!(vec!["example1.txt", "example2.txt"], day10);
+
+        // Days are in numerical order, not the order of their names.
+        let days: Vec<u32> = examples.iter().map(|e| e.day).collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+
+        assert_eq!(sorted, days);
+    }
+
+    // Examples numbered past 9 still come after those before them.
+    #[test]
+    fn discover_numeric_order() {
+        let root = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("day3")).unwrap();
+
+        for name in ["example10.txt", "example2.txt", "example1.txt", "notes.txt"] {
+            fs::write(root.join("day3").join(name), "").unwrap();
+        }
+
+        let names: Vec<String> = discover(&root).unwrap().iter().map(|e| e.name()).collect();
+        assert_eq!(vec!["notes.txt", "example1.txt", "example2.txt", "example10.txt"], names);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // Every example should give the answers registered for it,
+    // through the same parse and solve steps as the real input.
+    #[test]
+    fn examples() {
+        let examples = discover(Path::new(EXAMPLES_DIR)).unwrap();
+        let answers = answers(Path::new(EXAMPLES_DIR)).unwrap();
+
+        let mut failures = Vec::new();
+
+        for example in &examples {
+            // A new day's example, which hasn't been filled in yet.
+            if fs::metadata(&example.path).map(|m| m.len() == 0).unwrap_or(false) {
+                continue;
+            }
+
+            let puzzle = match days::get(example.day) {
+                Some(p) => p,
+                None => {
+                    failures.push(format!("{}: no solution for day {}", example.path.display(), example.day));
+                    continue;
+                }
+            };
+
+            let outcomes = match verify::verify(puzzle.as_ref(), &example.source(), &answers, &Part::both()) {
+                Ok(o) => o,
+                Err(e) => {
+                    failures.push(format!("{}: {}", example.path.display(), e));
+                    continue;
+                }
+            };
+
+            // An example with no answers isn't testing anything.
+            if outcomes.iter().all(|o| o.status == Status::Missing) {
+                failures.push(format!("{}: no answers registered", example.path.display()));
+            }
+
+            for o in outcomes.iter().filter(|o| o.status == Status::Fail) {
+                failures.push(format!("{}: part {}: expected {}, got {}",
+                    example.path.display(),
+                    o.part,
+                    o.expected.as_deref().unwrap_or("-"),
+                    o.answer.as_deref().unwrap_or("-")));
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+}
+
+// A puzzle example kept on disk, at <examples dir>/dayN/<name>.
+pub struct Example {
+    pub day: u32,
+    pub path: PathBuf
+}
+
+impl Example {
+    // The example's file name, which its answers are registered under.
+    pub fn name(&self) -> String {
+        return self.path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+    }
+
+    // The number the example's name ends in, e.g. 2 for example2.txt,
+    // if it ends in one.
+    fn number(&self) -> Option<u64> {
+        let stem = self.path.file_stem()?.to_str()?;
+        let digits = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+
+        return stem[digits.len()..].parse().ok();
+    }
+
+    // The source to read the example's input from.
+    pub fn source(&self) -> Source {
+        return Source::File(self.path.clone());
+    }
+}
+
+// Finds every example in an examples directory: each .txt file
+// in a subdirectory named after its day, e.g. day10/example1.txt.
+// Anything else is ignored.
+//
+// Examples are returned in order of day, then of the number at the end
+// of their name, so example2.txt comes before example10.txt.
+pub fn discover(dir: &Path) -> io::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+
+        let day = match entry.file_name().to_str().and_then(|n| n.strip_prefix("day")) {
+            Some(d) => match d.parse() {
+                Ok(d) => d,
+                Err(_) => continue
+            },
+            None => continue
+        };
+
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        for file in fs::read_dir(entry.path())? {
+            let path = file?.path();
+
+            if path.is_file() && path.extension().is_some_and(|e| e == "txt") {
+                examples.push(Example { day, path });
+            }
+        }
+    }
+
+    examples.sort_by_cached_key(|e| (e.day, e.number(), e.path.clone()));
+
+    return Ok(examples);
+}
+
+// Reads an example's input from the examples directory,
+// e.g. read(10, "example1.txt"), for a day's own tests to use.
+pub fn read(day: u32, name: &str) -> io::Result<String> {
+    return fs::read_to_string(Path::new(EXAMPLES_DIR).join(format!("day{}", day)).join(name));
+}
+
+// Loads the expected answers for the examples in an examples directory,
+// from the answer file at its top level.
+pub fn answers(dir: &Path) -> Result<Answers, String> {
+    return Answers::load(&dir.join(ANSWERS_FILE));
+}
@@ -0,0 +1,570 @@
+// Saving visualisations to files, as images or an animation.
+//
+// Each character in a frame becomes a square of pixels, in a colour
+// picked for it from a small fixed palette. Frames can be saved one
+// file each as PPM, PGM or SVG, or together as an animated GIF.
+// Everything is written by hand, so there's nothing to install.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::viz::{Frame, Renderer};
+
+#[cfg(test)]
+mod test_images {
+    use super::*;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(2, 1).with_caption("Step <1>");
+        frame.set(0, 0, '#');
+        return frame;
+    }
+
+    #[test]
+    fn ppm() {
+        let mut expected = b"P6\n4 2\n255\n".to_vec();
+        let (tree, blank) = (COLOURS[palette_index('#')], COLOURS[palette_index(' ')]);
+
+        for _ in 0..2 {
+            for colour in &[tree, tree, blank, blank] {
+                expected.extend(colour);
+            }
+        }
+
+        assert_eq!(expected, super::ppm(&frame(), 2));
+    }
+
+    #[test]
+    fn pgm() {
+        let image = super::pgm(&frame(), 1);
+
+        assert!(image.starts_with(b"P5\n2 1\n255\n"));
+        assert_eq!(2, image.len() - b"P5\n2 1\n255\n".len());
+
+        // Trees are darker than blank space.
+        assert!(image[image.len() - 2] < image[image.len() - 1]);
+    }
+
+    #[test]
+    fn svg() {
+        let image = super::svg(&frame(), 10);
+
+        assert!(image.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"10\">"));
+        assert!(image.contains("<title>Step &lt;1&gt;</title>"));
+        assert!(image.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#226633\"/>"));
+        assert!(image.trim_end().ends_with("</svg>"));
+
+        // Blank squares are left as the background.
+        assert_eq!(2, image.matches("<rect").count());
+    }
+
+    #[test]
+    fn format_names() {
+        assert_eq!(Some(ImageFormat::Gif), ImageFormat::from_name("gif"));
+        assert_eq!(Some(ImageFormat::Pgm), ImageFormat::from_name("pgm"));
+        assert_eq!(None, ImageFormat::from_name("png"));
+    }
+}
+
+#[cfg(test)]
+mod test_gif {
+    use super::*;
+
+    use crate::gen::Rng;
+
+    // A GIF LZW decoder, to check the encoder against.
+    fn decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = Vec::new();
+
+        let (mut bits, mut count, mut i) = (0u32, 0, 0);
+
+        loop {
+            while count < code_size {
+                bits |= (data[i] as u32) << count;
+                count += 8;
+                i += 1;
+            }
+
+            let code = (bits & ((1 << code_size) - 1)) as u16;
+            bits >>= code_size;
+            count -= code_size;
+
+            if code == clear {
+                table = (0..clear).map(|c| vec![c as u8]).collect();
+                table.push(vec![]);
+                table.push(vec![]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => {
+                    let mut e = p.clone();
+                    e.push(p[0]);
+                    e
+                },
+                (None, None) => panic!("Invalid code {}", code)
+            };
+
+            if let Some(p) = previous {
+                if table.len() < 4096 {
+                    let mut added = p.clone();
+                    added.push(entry[0]);
+                    table.push(added);
+
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+
+            output.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let mut rng = Rng::new(19);
+
+        // Long enough to fill the code table more than once.
+        let noisy: Vec<u8> = (0..50_000).map(|_| rng.below(16) as u8).collect();
+        assert_eq!(noisy, decode(&lzw(&noisy, 4), 4));
+
+        let flat = vec![3u8; 20_000];
+        assert_eq!(flat, decode(&lzw(&flat, 4), 4));
+
+        assert_eq!(vec![7u8], decode(&lzw(&[7], 4), 4));
+    }
+
+    #[test]
+    fn animation() {
+        let mut out = Vec::new();
+
+        {
+            let mut gif = Gif::new(&mut out, 2, 25);
+
+            gif.render(&Frame::new(3, 2)).unwrap();
+            gif.render(&Frame::new(3, 2)).unwrap();
+            gif.finish().unwrap();
+        }
+
+        assert!(out.starts_with(b"GIF89a"));
+
+        // Logical screen size, little-endian.
+        assert_eq!(&[6, 0, 4, 0], &out[6..10]);
+
+        assert!(out.windows(11).any(|w| w == b"NETSCAPE2.0"));
+        assert_eq!(2, out.windows(4).filter(|w| w == &[0x21, 0xf9, 4, 0]).count());
+
+        // The delay between frames, in hundredths of a second.
+        let control = out.windows(4).position(|w| w == [0x21, 0xf9, 4, 0]).unwrap();
+        assert_eq!(&[25, 0], &out[control + 4..control + 6]);
+
+        assert_eq!(Some(&0x3b), out.last());
+    }
+
+    // Nothing is written for a visualisation with no frames.
+    #[test]
+    fn no_frames() {
+        let mut out = Vec::new();
+
+        Gif::new(&mut out, 2, 10).finish().unwrap();
+
+        assert!(out.is_empty());
+    }
+}
+
+// The file format to save frames in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ImageFormat {
+    // One binary colour image per frame.
+    Ppm,
+
+    // One binary greyscale image per frame.
+    Pgm,
+
+    // One vector image per frame, with the caption as its title.
+    Svg,
+
+    // A single looping animation of every frame.
+    Gif
+}
+
+impl ImageFormat {
+    pub fn from_name(name: &str) -> Option<ImageFormat> {
+        return match name {
+            "ppm" => Some(ImageFormat::Ppm),
+            "pgm" => Some(ImageFormat::Pgm),
+            "svg" => Some(ImageFormat::Svg),
+            "gif" => Some(ImageFormat::Gif),
+            _ => None
+        };
+    }
+
+    pub fn extension(&self) -> &'static str {
+        return match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Svg => "svg",
+            ImageFormat::Gif => "gif"
+        };
+    }
+}
+
+// The palette every frame is drawn with. Characters the days draw with
+// have a colour of their own; anything else is drawn in black.
+const COLOURS: [[u8; 3]; 16] = [
+    [255, 255, 255], // ' ' background
+    [200, 200, 200], // '.' floor, open ground, track
+    [34, 102, 51],   // '#' tree, occupied seat
+    [70, 110, 180],  // 'L' empty seat
+    [240, 200, 40],  // 'O' toboggan on open ground
+    [200, 40, 40],   // 'X' toboggan hitting a tree
+    [230, 110, 20],  // '@' ship
+    [120, 60, 160],  // 'S' start
+    [30, 160, 170],  // 'W' waypoint
+    [0, 0, 0],       // anything else
+    [0, 0, 0],
+    [0, 0, 0],
+    [0, 0, 0],
+    [0, 0, 0],
+    [0, 0, 0],
+    [0, 0, 0]
+];
+
+fn palette_index(c: char) -> usize {
+    return match c {
+        ' ' => 0,
+        '.' => 1,
+        '#' => 2,
+        'L' => 3,
+        'O' => 4,
+        'X' => 5,
+        '@' => 6,
+        'S' => 7,
+        'W' => 8,
+        _ => 9
+    };
+}
+
+// The palette index of each pixel of a frame, in rows,
+// drawing each character as a <scale> pixel square.
+fn pixels(frame: &Frame, scale: usize) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(frame.width() * frame.height() * scale * scale);
+
+    for y in 0..frame.height() {
+        let row: Vec<u8> = frame.row(y).iter()
+            .flat_map(|&c| std::iter::repeat_n(palette_index(c) as u8, scale))
+            .collect();
+
+        for _ in 0..scale {
+            pixels.extend(&row);
+        }
+    }
+
+    return pixels;
+}
+
+// A frame as a binary PPM (P6) image.
+pub fn ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", frame.width() * scale, frame.height() * scale).into_bytes();
+
+    for index in pixels(frame, scale) {
+        image.extend(&COLOURS[index as usize]);
+    }
+
+    return image;
+}
+
+// A frame as a binary PGM (P5) image, using each colour's brightness.
+pub fn pgm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let mut image = format!("P5\n{} {}\n255\n", frame.width() * scale, frame.height() * scale).into_bytes();
+
+    for index in pixels(frame, scale) {
+        let [r, g, b] = COLOURS[index as usize];
+        image.push(((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8);
+    }
+
+    return image;
+}
+
+// A frame as an SVG image, with a square for each character
+// that isn't blank, and the caption as its title.
+pub fn svg(frame: &Frame, scale: usize) -> String {
+    let mut image = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        frame.width() * scale,
+        frame.height() * scale);
+
+    let escaped = frame.caption().replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    image.push_str(&format!("<title>{}</title>\n", escaped));
+
+    let [r, g, b] = COLOURS[0];
+    image.push_str(&format!("<rect width=\"100%\" height=\"100%\" fill=\"#{:02x}{:02x}{:02x}\"/>\n", r, g, b));
+
+    for y in 0..frame.height() {
+        for (x, &c) in frame.row(y).iter().enumerate() {
+            let index = palette_index(c);
+
+            if index == 0 {
+                continue;
+            }
+
+            let [r, g, b] = COLOURS[index];
+
+            image.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{:02x}{:02x}{:02x}\"/>\n",
+                x * scale, y * scale, scale, scale, r, g, b));
+        }
+    }
+
+    image.push_str("</svg>\n");
+    return image;
+}
+
+// Saves each frame to a numbered file of its own in a directory.
+pub struct Images {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    count: usize
+}
+
+impl Images {
+    // Only PPM, PGM and SVG can be saved one frame per file.
+    pub fn new(dir: &Path, format: ImageFormat, scale: usize) -> io::Result<Images> {
+        assert!(format != ImageFormat::Gif);
+
+        fs::create_dir_all(dir)?;
+
+        return Ok(Images { dir: dir.to_path_buf(), format, scale, count: 0 });
+    }
+}
+
+impl Renderer for Images {
+    fn render(&mut self, frame: &Frame) -> io::Result<()> {
+        self.count += 1;
+
+        let path = self.dir.join(format!("frame{:04}.{}", self.count, self.format.extension()));
+
+        let image = match self.format {
+            ImageFormat::Ppm => ppm(frame, self.scale),
+            ImageFormat::Pgm => pgm(frame, self.scale),
+            _ => svg(frame, self.scale).into_bytes()
+        };
+
+        return fs::write(path, image);
+    }
+}
+
+// Bits in each palette index.
+const GIF_COLOUR_BITS: u8 = 4;
+
+// Shortest time a GIF frame can be shown for, in hundredths of a second,
+// that viewers reliably respect.
+pub const MIN_GIF_DELAY: u16 = 2;
+
+// Writes frames as a looping animated GIF, a frame at a time.
+// The animation is the size of the first frame.
+pub struct Gif<W: Write> {
+    out: W,
+    scale: usize,
+
+    // Time each frame is shown for, in hundredths of a second.
+    delay: u16,
+
+    // The size of the animation, once the first frame has been written.
+    size: Option<(usize, usize)>
+}
+
+impl Gif<BufWriter<File>> {
+    pub fn create(path: &Path, scale: usize, delay: u16) -> io::Result<Gif<BufWriter<File>>> {
+        return Ok(Gif::new(BufWriter::new(File::create(path)?), scale, delay));
+    }
+}
+
+impl<W: Write> Gif<W> {
+    pub fn new(out: W, scale: usize, delay: u16) -> Gif<W> {
+        return Gif { out, scale, delay, size: None };
+    }
+
+    fn header(&mut self, width: usize, height: usize) -> io::Result<()> {
+        self.out.write_all(b"GIF89a")?;
+        self.out.write_all(&(width as u16).to_le_bytes())?;
+        self.out.write_all(&(height as u16).to_le_bytes())?;
+
+        // A global colour table of 2^4 colours, with a background colour of 0.
+        self.out.write_all(&[0xf0 | (GIF_COLOUR_BITS - 1), 0, 0])?;
+
+        for colour in COLOURS.iter() {
+            self.out.write_all(colour)?;
+        }
+
+        // Loop forever.
+        self.out.write_all(&[0x21, 0xff, 11])?;
+        self.out.write_all(b"NETSCAPE2.0")?;
+        return self.out.write_all(&[3, 1, 0, 0, 0]);
+    }
+}
+
+impl<W: Write> Renderer for Gif<W> {
+    fn render(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height) = match self.size {
+            Some(size) => size,
+            None => {
+                let size = ((frame.width() * self.scale).min(65535), (frame.height() * self.scale).min(65535));
+                self.header(size.0, size.1)?;
+                self.size = Some(size);
+                size
+            }
+        };
+
+        // Frames of a different size are cropped or padded to fit.
+        let mut fitted = Frame::new(width.div_ceil(self.scale), height.div_ceil(self.scale));
+
+        for y in 0..fitted.height() {
+            for x in 0..fitted.width() {
+                fitted.set(x, y, frame.get(x, y).unwrap_or(' '));
+            }
+        }
+
+        let pixels: Vec<u8> = pixels(&fitted, self.scale).chunks(fitted.width() * self.scale)
+            .take(height)
+            .flat_map(|row| row[..width].to_vec())
+            .collect();
+
+        // Graphic control extension, for the delay.
+        self.out.write_all(&[0x21, 0xf9, 4, 0])?;
+        self.out.write_all(&self.delay.to_le_bytes())?;
+        self.out.write_all(&[0, 0])?;
+
+        // Image descriptor, covering the whole animation.
+        self.out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.out.write_all(&(width as u16).to_le_bytes())?;
+        self.out.write_all(&(height as u16).to_le_bytes())?;
+        self.out.write_all(&[0])?;
+
+        self.out.write_all(&[GIF_COLOUR_BITS])?;
+
+        for block in lzw(&pixels, GIF_COLOUR_BITS).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+
+        return self.out.write_all(&[0]);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.size.is_some() {
+            self.out.write_all(&[0x3b])?;
+        }
+
+        return self.out.flush();
+    }
+}
+
+// Number of codes GIF's LZW compression can use.
+const MAX_CODES: u16 = 4096;
+
+// Packs codes of varying sizes into bytes, least significant bit first.
+struct Bits {
+    output: Vec<u8>,
+    bits: u32,
+    count: u8
+}
+
+impl Bits {
+    fn write(&mut self, code: u16, size: u8) {
+        self.bits |= (code as u32) << self.count;
+        self.count += size;
+
+        while self.count >= 8 {
+            self.output.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // The bytes written, with any bits left over padded to a whole byte.
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.output.push(self.bits as u8);
+        }
+
+        return self.output;
+    }
+}
+
+// Compresses palette indices with GIF's variant of LZW: variable-length
+// codes of up to 12 bits, starting again with a clear code whenever the
+// code table fills up.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = Bits { output: Vec::new(), bits: 0, count: 0 };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut code_size = min_code_size + 1;
+
+    out.write(clear, code_size);
+
+    let mut indices = indices.iter();
+
+    let mut current = match indices.next() {
+        Some(&i) => i as u16,
+        None => {
+            out.write(end, code_size);
+            return out.finish();
+        }
+    };
+
+    for &index in indices {
+        if let Some(&code) = table.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+
+        out.write(current, code_size);
+
+        if next < MAX_CODES {
+            // The decoder's table is a code behind, so the code size
+            // only goes up once the next code needs the extra bit.
+            if next == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+
+            table.insert((current, index), next);
+            next += 1;
+        } else {
+            out.write(clear, code_size);
+
+            table.clear();
+            next = end + 1;
+            code_size = min_code_size + 1;
+        }
+
+        current = index as u16;
+    }
+
+    out.write(current, code_size);
+    out.write(end, code_size);
+
+    return out.finish();
+}
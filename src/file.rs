@@ -1,36 +1,287 @@
 use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::io::{self, prelude::*, BufReader};
 
-// The result of trying to get a line from a File.
-pub enum FileReadResult {
-    Success(String),
-    Eof,
-    Err
+use regex::Regex;
+
+#[cfg(test)]
+mod test_grouped_file_reader {
+    use super::*;
+
+    fn groups(s: &str) -> Vec<Group> {
+        return GroupedFileReader::new(s.as_bytes())
+            .collect::<io::Result<Vec<Group>>>()
+            .expect("Reading from a string failed");
+    }
+
+    #[test]
+    fn single_group() {
+        let groups = groups("abc\ndef\n");
+
+        assert_eq!(1, groups.len());
+        assert_eq!(vec!["abc", "def"], groups[0].lines());
+    }
+
+    #[test]
+    fn no_trailing_newline() {
+        let groups = groups("abc\ndef");
+
+        assert_eq!(1, groups.len());
+        assert_eq!(vec!["abc", "def"], groups[0].lines());
+    }
+
+    #[test]
+    fn multiple_groups() {
+        let groups = groups("abc\ndef\n\nghi\n\njkl\nmno\n");
+
+        assert_eq!(3, groups.len());
+        assert_eq!(vec!["abc", "def"], groups[0].lines());
+        assert_eq!(vec!["ghi"], groups[1].lines());
+        assert_eq!(vec!["jkl", "mno"], groups[2].lines());
+    }
+
+    // Several blank lines in a row are a single separator,
+    // rather than producing empty groups.
+    #[test]
+    fn repeated_blank_lines() {
+        let groups = groups("\n\nabc\n\n\n  \ndef\n\n");
+
+        assert_eq!(2, groups.len());
+        assert_eq!(vec!["abc"], groups[0].lines());
+        assert_eq!(vec!["def"], groups[1].lines());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(0, groups("").len());
+        assert_eq!(0, groups("\n\n").len());
+    }
+
+    #[test]
+    fn text() {
+        let groups = groups("abc\ndef\n\nghi\n");
+
+        assert_eq!("abc\ndef", groups[0].text());
+        assert_eq!("ghi", groups[1].text());
+    }
+
+    // Reading invalid UTF-8 should give back the I/O error.
+    #[test]
+    fn io_error() {
+        let mut reader = GroupedFileReader::new(&b"abc\n\xff\xfe\n"[..]);
+
+        let err = reader.next().expect("No result returned.").expect_err("No error returned.");
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+
+    // Each group knows the line it started on, counting from 1.
+    #[test]
+    fn start_line() {
+        let groups = groups("\nabc\ndef\n\n\nghi\n");
+
+        assert_eq!(2, groups[0].start_line());
+        assert_eq!(6, groups[1].start_line());
+    }
+
+    #[test]
+    fn open_missing_file() {
+        let err = GroupedFileReader::open("data/no_such_file.txt").err().expect("No error returned.");
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+    }
 }
 
-// Specialized file-reader for reading "groups" of lines from
-// a file. A group is a series of lines separated by newline,
-// where all groups are themselves separated by blank lines.
-pub struct GroupedFileReader {
-    reader: BufReader<File>
+#[cfg(test)]
+mod test_options {
+    use super::*;
+
+    fn groups(reader: GroupedFileReader<&[u8]>) -> Vec<Group> {
+        return reader
+            .collect::<io::Result<Vec<Group>>>()
+            .expect("Reading from a string failed");
+    }
+
+    #[test]
+    fn crlf_stripped() {
+        let groups = groups(GroupedFileReader::new(&b"abc\r\ndef\r\n\r\nghi\r\n"[..]));
+
+        assert_eq!(2, groups.len());
+        assert_eq!(vec!["abc", "def"], groups[0].lines());
+        assert_eq!(vec!["ghi"], groups[1].lines());
+    }
+
+    #[test]
+    fn crlf_kept() {
+        let groups = groups(GroupedFileReader::new(&b"abc\r\ndef\r\n"[..]).strip_cr(false));
+        assert_eq!(vec!["abc\r", "def\r"], groups[0].lines());
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let input = &b"abc  \n  def\t\n"[..];
+
+        let kept = groups(GroupedFileReader::new(input));
+        assert_eq!(vec!["abc  ", "  def\t"], kept[0].lines());
+
+        // Only the end of each line is trimmed.
+        let trimmed = groups(GroupedFileReader::new(input).trim_trailing_whitespace(true));
+        assert_eq!(vec!["abc", "  def"], trimmed[0].lines());
+    }
+
+    // With a marker line as the delimiter, blank lines are
+    // part of a group like any other line.
+    #[test]
+    fn marker() {
+        let input = &b"abc\n\ndef\n---\nghi\n---\n---\njkl\n"[..];
+        let groups = groups(GroupedFileReader::new(input).delimiter(Delimiter::Marker(String::from("---"))));
+
+        assert_eq!(3, groups.len());
+        assert_eq!(vec!["abc", "", "def"], groups[0].lines());
+        assert_eq!(vec!["ghi"], groups[1].lines());
+        assert_eq!(vec!["jkl"], groups[2].lines());
+
+        assert_eq!(1, groups[0].start_line());
+        assert_eq!(5, groups[1].start_line());
+        assert_eq!(8, groups[2].start_line());
+    }
+
+    #[test]
+    fn pattern() {
+        let input = &b"Tile 1:\nab\ncd\nTile 22:\nef\n"[..];
+        let delimiter = Delimiter::Pattern(Regex::new(r"^Tile \d+:$").unwrap());
+
+        let groups = groups(GroupedFileReader::new(input).delimiter(delimiter));
+
+        assert_eq!(2, groups.len());
+        assert_eq!(vec!["ab", "cd"], groups[0].lines());
+        assert_eq!(vec!["ef"], groups[1].lines());
+        assert_eq!(5, groups[1].start_line());
+    }
 }
 
-impl GroupedFileReader {
-    pub fn open(path: &str) -> Result<GroupedFileReader, String> {
-        let file = match File::open(path) {
-            Ok(f) => f,
-            Err(_) => return Err(format!("Could not open file: {}", path))
+// How the end of one group and the start of the next are marked.
+#[derive(Debug, Clone)]
+pub enum Delimiter {
+    // A line which is empty or only whitespace.
+    BlankLine,
+
+    // A line which is exactly the given text.
+    Marker(String),
+
+    // A line which matches the given regex.
+    Pattern(Regex)
+}
+
+impl Delimiter {
+    fn matches(&self, line: &str) -> bool {
+        return match self {
+            Delimiter::BlankLine => line.trim().is_empty(),
+            Delimiter::Marker(marker) => line == marker,
+            Delimiter::Pattern(regex) => regex.is_match(line)
         };
+    }
+}
+
+// A group of consecutive lines between delimiters.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Group {
+    lines: Vec<String>,
+    start_line: usize
+}
+
+impl Group {
+    // The line of the input this group's first line is on,
+    // counting from 1.
+    pub fn start_line(&self) -> usize {
+        return self.start_line;
+    }
+
+    // The lines in this group, without line endings.
+    pub fn lines(&self) -> &[String] {
+        return &self.lines;
+    }
+
+    // The lines in this group, joined by newlines.
+    pub fn text(&self) -> String {
+        return self.lines.join("\n");
+    }
+}
+
+// Specialized reader for reading "groups" of lines from
+// any buffered source. A group is a series of lines separated by newline,
+// where all groups are themselves separated by blank lines, or another
+// delimiter if one is given. Several delimiters in a row are a single
+// separator, rather than producing empty groups.
+//
+// By default, the \r of CRLF line endings is removed, and other
+// whitespace is left alone.
+pub struct GroupedFileReader<R> {
+    reader: R,
+    delimiter: Delimiter,
+    strip_cr: bool,
+    trim_trailing_whitespace: bool,
+
+    // Lines read so far.
+    line_number: usize
+}
+
+impl GroupedFileReader<BufReader<File>> {
+    pub fn open(path: &str) -> io::Result<GroupedFileReader<BufReader<File>>> {
+        let file = File::open(path)?;
 
-        let file = GroupedFileReader {
-            reader: BufReader::new(file)
+        return Ok(GroupedFileReader::new(BufReader::new(file)));
+    }
+}
+
+impl<R: BufRead> GroupedFileReader<R> {
+    pub fn new(reader: R) -> GroupedFileReader<R> {
+        return GroupedFileReader {
+            reader,
+            delimiter: Delimiter::BlankLine,
+            strip_cr: true,
+            trim_trailing_whitespace: false,
+            line_number: 0
         };
+    }
+
+    pub fn delimiter(mut self, delimiter: Delimiter) -> GroupedFileReader<R> {
+        self.delimiter = delimiter;
+        return self;
+    }
 
-        return Ok(file);
+    // Whether to remove the \r from lines ending in \r\n.
+    pub fn strip_cr(mut self, strip: bool) -> GroupedFileReader<R> {
+        self.strip_cr = strip;
+        return self;
     }
 
-    pub fn next_group(&mut self) -> FileReadResult {
-        let mut s = String::new();
+    // Whether to remove whitespace from the end of each line.
+    pub fn trim_trailing_whitespace(mut self, trim: bool) -> GroupedFileReader<R> {
+        self.trim_trailing_whitespace = trim;
+        return self;
+    }
+
+    // A line as read, without its line ending and normalised
+    // as asked for.
+    fn normalise<'a>(&self, line: &'a str) -> &'a str {
+        let mut line = line.strip_suffix('\n').unwrap_or(line);
+
+        if self.strip_cr {
+            line = line.strip_suffix('\r').unwrap_or(line);
+        }
+
+        if self.trim_trailing_whitespace {
+            line = line.trim_end();
+        }
+
+        return line;
+    }
+}
+
+impl<R: BufRead> Iterator for GroupedFileReader<R> {
+    type Item = io::Result<Group>;
+
+    fn next(&mut self) -> Option<io::Result<Group>> {
+        let mut lines = Vec::new();
+        let mut start_line = 0;
         let mut l = String::new();
 
         loop {
@@ -38,29 +289,40 @@ impl GroupedFileReader {
 
             match self.reader.read_line(&mut l) {
                 // Reached end of file.
-                // Return EOF if we didn't collect any lines
+                // Return None if we didn't collect any lines
                 // otherwise, return the lines collected.
-                Ok(0) => { // End-of-file.
-                    if s.is_empty() {
-                        return FileReadResult::Eof;
+                Ok(0) => {
+                    if lines.is_empty() {
+                        return None;
                     } else {
-                        return FileReadResult::Success(s);
+                        return Some(Ok(Group { lines, start_line }));
                     }
                 }
-                
+
                 // Normal read.
                 Ok(_) => {
-                    // If the line we read is completely whitespace,
-                    // that's the end of this group.
-                    if l.trim().is_empty() {
-                        return FileReadResult::Success(s);
+                    self.line_number += 1;
+
+                    // If the line is a delimiter, that's the end
+                    // of this group. Delimiters before the group
+                    // has started are skipped.
+                    let line = self.normalise(&l);
+
+                    if self.delimiter.matches(line) {
+                        if !lines.is_empty() {
+                            return Some(Ok(Group { lines, start_line }));
+                        }
                     } else {
-                        s.push_str(&l);
+                        if lines.is_empty() {
+                            start_line = self.line_number;
+                        }
+
+                        lines.push(String::from(line));
                     }
                 }
 
                 // Error. Return error.
-                Err(_) => return FileReadResult::Err
+                Err(e) => return Some(Err(e))
             }
         }
     }
@@ -0,0 +1,743 @@
+// Random but valid puzzle inputs, for stress tests, scaling
+// benchmarks and fuzzing the parsers.
+//
+// Every generator takes a random number generator and a size,
+// and gives an input which parses and (except where noted)
+// can be solved. The same seed and size always give the same input.
+// What the size means depends on the day, e.g. the number of lines
+// or the width of a grid. Sizes outside what a day can handle, such
+// as more distinct numbers than fit below 2020 for day 1, are clamped.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+#[cfg(test)]
+mod test_rng {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn range_inclusive() {
+        let mut rng = Rng::new(7);
+        let mut seen = HashSet::new();
+
+        for _ in 0..1000 {
+            let n = rng.range(3, 6);
+            assert!((3..=6).contains(&n));
+            seen.insert(n);
+        }
+
+        assert_eq!(4, seen.len());
+    }
+
+    #[test]
+    fn shuffle_keeps_items() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!((0..50).collect::<Vec<u32>>(), items);
+
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<u32>>(), items);
+    }
+}
+
+#[cfg(test)]
+mod test_inputs {
+    use super::*;
+    use regex::Regex;
+
+    use crate::days;
+    use crate::solution::Part;
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=15 {
+            assert_eq!(input(day, 5, 20), input(day, 5, 20), "Day {}", day);
+        }
+
+        assert_ne!(input(7, 5, 20), input(7, 6, 20));
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert_eq!(None, input(26, 1, 10));
+    }
+
+    // Every generated input should parse, and both parts solve.
+    #[test]
+    fn solvable() {
+        for seed in 0..5 {
+            for puzzle in days::all() {
+                let day = puzzle.day();
+
+                // A new day may not have a generator yet.
+                let input = match input(day, seed, 30) {
+                    Some(i) => i,
+                    None => continue
+                };
+
+                let parsed = match puzzle.parse(&input) {
+                    Ok(p) => p,
+                    Err(e) => panic!("Day {} seed {}: {}\n{}", day, seed, e, e.render(&input))
+                };
+
+                for part in Part::both().iter() {
+                    // Day 15 part 2 takes too long in a debug build.
+                    if day == 15 && *part == Part::Two {
+                        continue;
+                    }
+
+                    if let Err(e) = parsed.solve(*part) {
+                        panic!("Day {} part {} seed {}: {}", day, part, seed, e);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn day2_grammar() {
+        let re = Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").unwrap();
+        let input = day2(&mut Rng::new(1), 100);
+
+        assert_eq!(100, input.lines().count());
+
+        for line in input.lines() {
+            let captures = re.captures(line).expect(line);
+
+            let least: usize = captures[1].parse().unwrap();
+            let most: usize = captures[2].parse().unwrap();
+
+            assert!(1 <= least && least <= most && most <= captures[4].len(), "{}", line);
+        }
+    }
+
+    // Day 8's programs loop, but can be fixed by a single patch.
+    #[test]
+    fn day8_one_patch() {
+        for seed in 0..20 {
+            let input = day8(&mut Rng::new(seed), 50);
+            let puzzle = days::get(8).unwrap();
+
+            let parsed = puzzle.parse(&input).unwrap();
+            assert!(parsed.solve(Part::One).is_ok(), "Seed {}", seed);
+            assert!(parsed.solve(Part::Two).is_ok(), "Seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn day11_size() {
+        let input = day11(&mut Rng::new(1), 12);
+
+        assert_eq!(12, input.lines().count());
+        assert!(input.lines().all(|l| l.len() == 12 && l.chars().all(|c| c == 'L' || c == '.')));
+    }
+}
+
+// A small, fast, seedable random number generator (SplitMix64).
+// Not suitable for anything which needs to be unpredictable.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        return Rng { state: seed };
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+        return z ^ (z >> 31);
+    }
+
+    // A number from 0 up to, but not including, n.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0);
+
+        return ((self.next_u64() as u128 * n as u128) >> 64) as u64;
+    }
+
+    // A number from low to high, inclusive.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high);
+
+        if high - low == u64::MAX {
+            return self.next_u64();
+        }
+
+        return low + self.below(high - low + 1);
+    }
+
+    // True with the given probability.
+    pub fn chance(&mut self, p: f64) -> bool {
+        return ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p;
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.below(items.len() as u64) as usize];
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+// Generates an input for the given day, if there is a generator for it.
+pub fn input(day: u32, seed: u64, size: usize) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+
+    let input = match day {
+        1 => day1(rng, size),
+        2 => day2(rng, size),
+        3 => day3(rng, size),
+        4 => day4(rng, size),
+        5 => day5(rng, size),
+        6 => day6(rng, size),
+        7 => day7(rng, size),
+        8 => day8(rng, size),
+        9 => day9(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => day14(rng, size),
+        15 => day15(rng, size),
+        _ => return None
+    };
+
+    return Some(input);
+}
+
+// <size> distinct numbers below 2020, including a pair and
+// a triple which sum to 2020. At most a thousand or so numbers
+// can be chosen without another pair summing to 2020.
+pub fn day1(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(5, 1000);
+
+    // 1010 would pair with itself.
+    let mut numbers: HashSet<u64> = HashSet::new();
+    numbers.insert(1010);
+
+    let pair = loop {
+        let a = rng.range(1, 2019);
+
+        if a != 1010 {
+            break [a, 2020 - a];
+        }
+    };
+
+    numbers.extend(&pair);
+
+    let triple = loop {
+        let a = rng.range(1, 2017);
+        let b = rng.range(1, 2019 - a);
+        let triple = [a, b, 2020 - a - b];
+
+        let distinct: HashSet<u64> = triple.iter().copied().collect();
+
+        if distinct.len() == 3 && triple.iter().all(|n| !numbers.contains(n)) {
+            break triple;
+        }
+    };
+
+    numbers.extend(&triple);
+    numbers.extend(triple.iter().map(|n| 2020 - n));
+
+    let mut chosen: Vec<u64> = pair.iter().chain(triple.iter()).copied().collect();
+
+    // No other pair sums to 2020, so part 1 has a single answer.
+    while chosen.len() < size {
+        let n = rng.range(1, 2019);
+
+        if !numbers.contains(&n) {
+            numbers.insert(n);
+            numbers.insert(2020 - n);
+            chosen.push(n);
+        }
+    }
+
+    rng.shuffle(&mut chosen);
+
+    return lines(chosen.iter());
+}
+
+// <size> lines of <least>-<most> <char>: <password>.
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let least = rng.range(1, 8);
+        let most = rng.range(least, least + 8);
+
+        let character = letter(rng, 6);
+
+        // Long enough for both positions to be in the password,
+        // and biased towards the rule's character so that
+        // some passwords are valid.
+        let length = rng.range(most, most + 6);
+        let password: String = (0..length)
+            .map(|_| if rng.chance(0.4) { character } else { letter(rng, 6) })
+            .collect();
+
+        writeln!(input, "{}-{} {}: {}", least, most, character, password).unwrap();
+    }
+
+    return input;
+}
+
+// A map 31 squares wide and <size> high, about a quarter trees.
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size.max(1) {
+        let row: String = (0..31).map(|_| if rng.chance(0.25) { '#' } else { '.' }).collect();
+        writeln!(input, "{}", row).unwrap();
+    }
+
+    return input;
+}
+
+// <size> passports, some missing fields, some with invalid values,
+// with their fields spread over one to three lines.
+pub fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut passports = Vec::new();
+
+    for _ in 0..size {
+        let mut fields = Vec::new();
+
+        for &key in &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+            if rng.chance(0.1) {
+                continue;
+            }
+
+            let valid = rng.chance(0.8);
+            fields.push(format!("{}:{}", key, passport_value(rng, key, valid)));
+        }
+
+        rng.shuffle(&mut fields);
+
+        let mut passport = String::new();
+
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+
+            passport.push_str(field);
+        }
+
+        passports.push(passport);
+    }
+
+    return passports.join("\n\n") + "\n";
+}
+
+fn passport_value(rng: &mut Rng, key: &str, valid: bool) -> String {
+    return match (key, valid) {
+        ("byr", true) => rng.range(1920, 2002).to_string(),
+        ("byr", false) => rng.range(1850, 1919).to_string(),
+        ("iyr", true) => rng.range(2010, 2020).to_string(),
+        ("iyr", false) => rng.range(2021, 2030).to_string(),
+        ("eyr", true) => rng.range(2020, 2030).to_string(),
+        ("eyr", false) => rng.range(1990, 2019).to_string(),
+        ("hgt", true) => {
+            if rng.chance(0.5) {
+                format!("{}cm", rng.range(150, 193))
+            } else {
+                format!("{}in", rng.range(59, 76))
+            }
+        },
+        ("hgt", false) => rng.range(100, 200).to_string(),
+        ("hcl", true) => format!("#{:06x}", rng.below(1 << 24)),
+        ("hcl", false) => format!("{:06x}", rng.below(1 << 24)),
+        ("ecl", true) => String::from(*rng.choose(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])),
+        ("ecl", false) => String::from(*rng.choose(&["xry", "zzz", "red", "gmt"])),
+        ("pid", true) => format!("{:09}", rng.below(1_000_000_000)),
+        ("pid", false) => format!("{}", rng.below(100_000_000)),
+        _ => rng.range(100, 350).to_string()
+    };
+}
+
+// <size> boarding passes, for a block of consecutive seats
+// with exactly one missing.
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, 1000) as u64;
+
+    // Seats either side of the block are missing too,
+    // so there's only one seat with neighbours on both sides.
+    let first = rng.range(1, 1022 - size);
+    let missing = rng.range(first + 1, first + size - 1);
+
+    let mut ids: Vec<u64> = (first..=first + size).filter(|&id| id != missing).collect();
+    rng.shuffle(&mut ids);
+
+    let mut input = String::new();
+
+    for id in ids {
+        let pass: String = (0..10).map(|bit| {
+            let set = id & (1 << (9 - bit)) != 0;
+
+            return match (bit < 7, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R'
+            };
+        }).collect();
+
+        writeln!(input, "{}", pass).unwrap();
+    }
+
+    return input;
+}
+
+// <size> groups of one to five people, each answering yes
+// to a few questions.
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    let mut groups = Vec::new();
+
+    for _ in 0..size {
+        let people: Vec<String> = (0..rng.range(1, 5)).map(|_| {
+            let mut questions: Vec<char> = ('a'..='z').collect();
+            rng.shuffle(&mut questions);
+
+            return questions[..rng.range(1, 26) as usize].iter().collect();
+        }).collect();
+
+        groups.push(people.join("\n"));
+    }
+
+    return groups.join("\n\n") + "\n";
+}
+
+const ADJECTIVES: [&str; 20] = [
+    "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant", "clear", "dim",
+    "dull", "drab", "mirrored", "pale", "plaid", "posh", "striped", "wavy", "dusty", "shaded"
+];
+
+const COLOURS: [&str; 20] = [
+    "red", "orange", "white", "yellow", "gold", "olive", "plum", "blue", "black", "green",
+    "teal", "tan", "lime", "cyan", "violet", "silver", "bronze", "maroon", "coral", "beige"
+];
+
+// Rules for <size> bags, including shiny gold, where no bag can
+// end up inside itself. The number of bags inside any one bag is
+// kept well within a u32.
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    const MOST_INSIDE: u64 = 1_000_000;
+
+    let size = size.clamp(1, ADJECTIVES.len() * COLOURS.len());
+
+    let mut names: Vec<String> = Vec::new();
+
+    for adjective in ADJECTIVES.iter() {
+        for colour in COLOURS.iter() {
+            let name = format!("{} {}", adjective, colour);
+
+            if name != "shiny gold" {
+                names.push(name);
+            }
+        }
+    }
+
+    rng.shuffle(&mut names);
+    names.truncate(size - 1);
+
+    let at = rng.below(size as u64) as usize;
+    names.insert(at, String::from("shiny gold"));
+
+    // Bags only contain bags later in the list, so there are no cycles.
+    // Working backwards, the number inside each later bag is known.
+    let mut inside = vec![0; size];
+    let mut rules = vec![String::new(); size];
+
+    for i in (0..size).rev() {
+        let mut contents = Vec::new();
+        let mut chosen = HashSet::new();
+
+        if i + 1 < size {
+            for _ in 0..rng.range(0, 3) {
+                let j = rng.range(i as u64 + 1, size as u64 - 1) as usize;
+                let count = rng.range(1, 4);
+
+                let total = inside[i] + count * (1 + inside[j]);
+
+                if total > MOST_INSIDE || !chosen.insert(j) {
+                    continue;
+                }
+
+                inside[i] = total;
+                contents.push(format!("{} {} {}", count, names[j], if count == 1 { "bag" } else { "bags" }));
+            }
+        }
+
+        let contents = if contents.is_empty() { String::from("no other bags") } else { contents.join(", ") };
+        rules[i] = format!("{} bags contain {}.", names[i], contents);
+    }
+
+    rng.shuffle(&mut rules);
+
+    return lines(rules.iter());
+}
+
+// A program of <size> instructions which loops forever, but
+// terminates if one of its jmp instructions is changed to a nop.
+pub fn day8(rng: &mut Rng, size: usize) -> String {
+    #[derive(Clone, Copy)]
+    enum Op { Acc, Jmp, Nop }
+
+    let size = size.max(2) as i64;
+
+    // Jumps only go forwards, so this terminates.
+    let mut program: Vec<(Op, i64)> = (0..size).map(|i| {
+        return match rng.below(10) {
+            0..=3 => (Op::Acc, rng.range(0, 100) as i64 - 50),
+            4..=6 => (Op::Nop, rng.range(0, 2 * size as u64) as i64 - size),
+            _ => (Op::Jmp, rng.range(1, 4.min(size - i) as u64) as i64)
+        };
+    }).collect();
+
+    // The instructions run, in order.
+    let path = |program: &[(Op, i64)]| {
+        let mut path = Vec::new();
+        let mut i = 0;
+
+        while i < size {
+            path.push(i);
+            i += match program[i as usize] { (Op::Jmp, n) => n, _ => 1 };
+        }
+
+        return path;
+    };
+
+    // Replace an instruction that is run with a nop, then turn that
+    // into a jump back to an instruction run before it (or itself).
+    // Changing the jump back to a nop lets the program finish.
+    let run = path(&program);
+    let at = *rng.choose(&run);
+    program[at as usize] = (Op::Nop, 0);
+
+    let run = path(&program);
+    let before: Vec<i64> = run.into_iter().filter(|&i| i <= at).collect();
+    let target = *rng.choose(&before);
+
+    program[at as usize] = (Op::Jmp, target - at);
+
+    let instructions = program.iter().map(|(op, n)| {
+        let op = match op { Op::Acc => "acc", Op::Jmp => "jmp", Op::Nop => "nop" };
+        return format!("{} {:+}", op, n);
+    });
+
+    return lines(instructions);
+}
+
+// A preamble of 25 numbers followed by numbers which are each
+// the sum of two of the 25 before them, <size> in all, ending
+// with one which isn't but is the sum of a run of earlier numbers.
+pub fn day9(rng: &mut Rng, size: usize) -> String {
+    const PREAMBLE: usize = 25;
+
+    let size = size.clamp(PREAMBLE + 2, 1000);
+
+    let mut numbers: Vec<u64> = Vec::new();
+
+    while numbers.len() < PREAMBLE {
+        let n = rng.range(1, 50);
+
+        if !numbers.contains(&n) {
+            numbers.push(n);
+        }
+    }
+
+    while numbers.len() < size - 1 {
+        // Adding two of the smallest keeps the numbers from growing
+        // too fast. Numbers in the window are kept distinct.
+        let mut window: Vec<u64> = numbers[numbers.len() - PREAMBLE..].to_vec();
+        window.sort();
+
+        let a = rng.below(5) as usize;
+        let b = rng.range(a as u64 + 1, 5) as usize;
+        let n = window[a] + window[b];
+
+        // The two largest always add up to something new.
+        if window.contains(&n) {
+            numbers.push(window[PREAMBLE - 2] + window[PREAMBLE - 1]);
+        } else {
+            numbers.push(n);
+        }
+    }
+
+    loop {
+        let start = rng.below(numbers.len() as u64 - 1) as usize;
+        let end = (start + rng.range(2, 5) as usize).min(numbers.len());
+        let invalid: u64 = numbers[start..end].iter().sum();
+
+        let window = &numbers[numbers.len() - PREAMBLE..];
+        let valid = window.iter().any(|&a| a < invalid && a * 2 != invalid && window.contains(&(invalid - a)));
+
+        if end - start >= 2 && !valid {
+            numbers.push(invalid);
+            break;
+        }
+    }
+
+    return lines(numbers.iter());
+}
+
+// <size> adapters which chain together with differences of 1 or 3.
+// Runs of differences of 1 are kept short so that the number of
+// arrangements fits in a u64.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, 100);
+
+    let mut adapters = Vec::new();
+    let mut joltage = 0;
+    let mut run = 0;
+
+    while adapters.len() < size {
+        let difference = if run < 3 && rng.chance(0.6) { 1 } else { 3 };
+        run = if difference == 1 { run + 1 } else { 0 };
+
+        joltage += difference;
+        adapters.push(joltage);
+    }
+
+    rng.shuffle(&mut adapters);
+
+    return lines(adapters.iter());
+}
+
+// A seat layout <size> seats square, mostly seats.
+//
+// Under part 1's rules, seats in a large enough random layout can
+// end up flipping back and forth forever, so part 1 may never finish.
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size.max(1) {
+        let row: String = (0..size.max(1)).map(|_| if rng.chance(0.75) { 'L' } else { '.' }).collect();
+        writeln!(input, "{}", row).unwrap();
+    }
+
+    return input;
+}
+
+// <size> navigation instructions, turning by quarter turns.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let actions = (0..size).map(|_| {
+        let action = *rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F']);
+
+        let value = match action {
+            'L' | 'R' => 90 * rng.range(1, 3),
+            _ => rng.range(1, 100)
+        };
+
+        return format!("{}{}", action, value);
+    });
+
+    return lines(actions);
+}
+
+const PRIMES: [u64; 12] = [7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+// An arrival time and a schedule of <size> slots, with buses
+// whose IDs are distinct primes in some of them, including the first.
+//
+// With more than a few buses, part 2 takes a very long time.
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    let mut primes = PRIMES.to_vec();
+    rng.shuffle(&mut primes);
+
+    let mut slots = vec![String::from("x"); size];
+    let buses = (size / 4).clamp(1, 4);
+
+    // The first slot always has a bus, the others are random.
+    let mut positions: Vec<usize> = (1..size).collect();
+    rng.shuffle(&mut positions);
+    positions.truncate(buses - 1);
+    positions.push(0);
+
+    for (position, id) in positions.iter().zip(primes.iter()) {
+        slots[*position] = id.to_string();
+    }
+
+    return format!("{}\n{}\n", rng.range(100, 1_000_000), slots.join(","));
+}
+
+// <size> stores, with a new mask every few. Masks have only a few
+// floating bits, so that part 2 writes to a reasonable number of addresses.
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let mut stores = 0;
+
+    while stores < size {
+        let mut mask: Vec<char> = (0..36).map(|_| if rng.chance(0.5) { '0' } else { '1' }).collect();
+
+        for _ in 0..rng.range(0, 6) {
+            let bit = rng.below(36) as usize;
+            mask[bit] = 'X';
+        }
+
+        writeln!(input, "mask = {}", mask.iter().collect::<String>()).unwrap();
+
+        for _ in 0..rng.range(1, 5) {
+            writeln!(input, "mem[{}] = {}", rng.below(65536), rng.below(1 << 36)).unwrap();
+            stores += 1;
+        }
+    }
+
+    return input;
+}
+
+// <size> distinct starting numbers.
+pub fn day15(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    let mut numbers: Vec<u64> = (0..size as u64 * 2).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(size);
+
+    let numbers: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+
+    return numbers.join(",") + "\n";
+}
+
+// A random lowercase letter, from the first <n> of the alphabet.
+fn letter(rng: &mut Rng, n: u8) -> char {
+    return (b'a' + rng.below(n as u64) as u8) as char;
+}
+
+// One item per line.
+fn lines<T: ToString, I: Iterator<Item = T>>(items: I) -> String {
+    let mut input = String::new();
+
+    for item in items {
+        writeln!(input, "{}", item.to_string()).unwrap();
+    }
+
+    return input;
+}
@@ -0,0 +1,383 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+// Offsets to the four orthogonal neighbours of a cell: N, E, S, W.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// Offsets to all eight neighbours of a cell, clockwise from N.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (0, -1), (1, -1), (1, 0), (1, 1),
+    (0, 1), (-1, 1), (-1, 0), (-1, -1)
+];
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    fn digit(c: char) -> Option<u32> {
+        return c.to_digit(10);
+    }
+
+    #[test]
+    fn valid() {
+        let grid = Grid::parse("123\n456", digit).expect("Valid grid rejected");
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(1, grid[(0, 0)]);
+        assert_eq!(6, grid[(2, 1)]);
+    }
+
+    // Leading blank lines and indentation are ignored,
+    // as they are in the examples written into tests.
+    #[test]
+    fn indented() {
+        let grid = Grid::parse("
+        12
+        34", digit).expect("Valid grid rejected");
+
+        assert_eq!(2, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(3, grid[(0, 1)]);
+    }
+
+    #[test]
+    fn empty() {
+        let err = Grid::parse("\n  \n", digit).expect_err("No error returned.");
+        assert_eq!("Invalid grid: empty", err.to_string());
+    }
+
+    #[test]
+    fn different_lengths() {
+        let err = Grid::parse("123\n45", digit).expect_err("No error returned.");
+        assert_eq!("Line 2: Invalid grid: lines of different lengths", err.to_string());
+    }
+
+    // Errors give the position in the untrimmed input.
+    #[test]
+    fn unsupported_character() {
+        let err = Grid::parse("\n  123\n  4x6", digit).expect_err("No error returned.");
+        assert_eq!("Line 3, column 4: Invalid grid: unsupported character", err.to_string());
+    }
+
+    // Columns count bytes, so characters before the error which take
+    // up more than one byte move it along further.
+    #[test]
+    fn unsupported_character_non_ascii() {
+        let letter = |c: char| if c.is_alphabetic() { Some(c) } else { None };
+
+        let err = Grid::parse("éé1", letter).expect_err("No error returned.");
+        assert_eq!("Line 1, column 5: Invalid grid: unsupported character", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_access {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        return Grid::parse("abc\ndef", Some).unwrap();
+    }
+
+    #[test]
+    fn get() {
+        let grid = grid();
+
+        assert_eq!(Some(&'a'), grid.get(0, 0));
+        assert_eq!(Some(&'f'), grid.get(2, 1));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(None, grid.get(0, 2));
+    }
+
+    #[test]
+    fn get_signed() {
+        let grid = grid();
+
+        assert_eq!(Some(&'e'), grid.get_signed(1, 1));
+        assert_eq!(None, grid.get_signed(-1, 0));
+        assert_eq!(None, grid.get_signed(0, -1));
+    }
+
+    #[test]
+    fn get_wrapping() {
+        let grid = grid();
+
+        assert_eq!(&'a', grid.get_wrapping(3, 0));
+        assert_eq!(&'f', grid.get_wrapping(-1, -1));
+        assert_eq!(&'e', grid.get_wrapping(7, 5));
+    }
+
+    #[test]
+    fn set() {
+        let mut grid = grid();
+        grid[(1, 0)] = 'x';
+
+        assert_eq!("axc\ndef\n", grid.to_string());
+    }
+
+    #[test]
+    fn iter_row_major() {
+        let cells: String = grid().iter().map(|(_, &c)| c).collect();
+        assert_eq!("abcdef", cells);
+
+        let grid = grid();
+        assert_eq!(Some(((2, 1), &'f')), grid.iter().last());
+    }
+
+    #[test]
+    fn map() {
+        let upper = grid().map(|(x, _), c| if x == 0 { c.to_ascii_uppercase() } else { *c });
+        assert_eq!("Abc\nDef\n", upper.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_neighbours {
+    use super::*;
+
+    fn grid() -> Grid<u32> {
+        return Grid::parse("123\n456\n789", |c| c.to_digit(10)).unwrap();
+    }
+
+    #[test]
+    fn four_in_middle() {
+        let n: Vec<u32> = grid().neighbours4(1, 1).map(|(x, y)| grid()[(x, y)]).collect();
+        assert_eq!(vec![2, 6, 8, 4], n);
+    }
+
+    #[test]
+    fn four_in_corner() {
+        let n: Vec<(usize, usize)> = grid().neighbours4(0, 0).collect();
+        assert_eq!(vec![(1, 0), (0, 1)], n);
+    }
+
+    #[test]
+    fn eight_in_middle() {
+        let n: Vec<u32> = grid().neighbours8(1, 1).map(|(x, y)| grid()[(x, y)]).collect();
+        assert_eq!(vec![2, 3, 6, 9, 8, 7, 4, 1], n);
+    }
+
+    #[test]
+    fn eight_on_edge() {
+        let n: Vec<u32> = grid().neighbours8(2, 1).map(|(x, y)| grid()[(x, y)]).collect();
+        assert_eq!(vec![3, 9, 8, 5, 2], n);
+    }
+
+    // The starting cell isn't part of the ray.
+    #[test]
+    fn ray() {
+        let grid = grid();
+
+        let ray: Vec<u32> = grid.ray(0, 0, 1, 1).map(|(_, &v)| v).collect();
+        assert_eq!(vec![5, 9], ray);
+
+        let ray: Vec<u32> = grid.ray(2, 1, -1, 0).map(|(_, &v)| v).collect();
+        assert_eq!(vec![5, 4], ray);
+
+        assert_eq!(0, grid.ray(1, 0, 0, -1).count());
+    }
+}
+
+// A rectangular grid of cells, stored densely in row-major order.
+// Positions are (x, y), with (0, 0) at the top left.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    // Creates a grid from its cells in row-major order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(width * height, cells.len());
+
+        return Grid { width, height, cells };
+    }
+
+    // Parses a grid from a map of characters, one row per line,
+    // using a function to turn each character into a cell.
+    // The function returns None for characters which aren't allowed.
+    //
+    // Blank lines before and after the grid, and whitespace around
+    // each line, are ignored.
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Grid<T>>
+        where F: FnMut(char) -> Option<T> {
+        if s.trim().is_empty() {
+            return Err(Error::parse("Invalid grid: empty"));
+        }
+
+        // Lines before the grid starts, so that errors give
+        // the line number in the untrimmed input.
+        let skipped_lines = s[..s.len() - s.trim_start().len()].matches('\n').count();
+
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (i, line) in s.trim().lines().enumerate() {
+            let line_number = skipped_lines + i + 1;
+            let indent = line.len() - line.trim_start().len();
+
+            let line = line.trim();
+            let length = line.chars().count();
+
+            if i > 0 && length != width {
+                return Err(Error::parse("Invalid grid: lines of different lengths").at_line(line_number));
+            }
+
+            width = length;
+
+            for (offset, c) in line.char_indices() {
+                match f(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let err = Error::parse("Invalid grid: unsupported character").with_text(c);
+                        return Err(err.at_line(line_number).at_column(indent + offset + 1));
+                    }
+                }
+            }
+
+            height += 1;
+        }
+
+        return Ok(Grid { width, height, cells });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    // The cell at a position, or None if it is outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        return Some(&self.cells[y * self.width + x]);
+    }
+
+    // As get(), for positions which may be negative.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        return self.get(x as usize, y as usize);
+    }
+
+    // The cell at a position, treating the grid as repeating
+    // forever in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+
+        return &self.cells[y * self.width + x];
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        return Some(&mut self.cells[y * self.width + x]);
+    }
+
+    // Every position and its cell, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+
+        return self.cells.iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell));
+    }
+
+    // A grid of the same size, with each cell given by
+    // a function of the position and cell in this one.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+        where F: FnMut((usize, usize), &T) -> U {
+        let cells = self.iter().map(|(p, cell)| f(p, cell)).collect();
+
+        return Grid { width: self.width, height: self.height, cells };
+    }
+
+    // The positions of the cells offset from a position
+    // by each of the given offsets, leaving out those outside the grid.
+    fn offsets<'a>(&self, x: usize, y: usize, offsets: &'a [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (x, y) = (x as isize, y as isize);
+
+        return offsets.iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(move |&(x, y)| x >= 0 && y >= 0 && x < width && y < height)
+            .map(|(x, y)| (x as usize, y as usize));
+    }
+
+    // The positions orthogonally next to a position, clockwise from N.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        return self.offsets(x, y, &NEIGHBOURS4);
+    }
+
+    // The positions next to a position, including diagonally,
+    // clockwise from N.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        return self.offsets(x, y, &NEIGHBOURS8);
+    }
+
+    // The cells in a straight line from a position, one step of
+    // (dx, dy) at a time, until the edge of the grid.
+    // The starting position isn't included.
+    pub fn ray(&self, x: usize, y: usize, dx: isize, dy: isize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        assert!(dx != 0 || dy != 0);
+
+        let mut x = x as isize;
+        let mut y = y as isize;
+
+        return std::iter::from_fn(move || {
+            x += dx;
+            y += dy;
+
+            return self.get_signed(x, y).map(|cell| ((x as usize, y as usize), cell));
+        });
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        return self.get(x, y).unwrap_or_else(|| panic!("Position ({}, {}) outside grid", x, y));
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        return self.get_mut(x, y).unwrap_or_else(|| panic!("Position ({}, {}) outside grid", x, y));
+    }
+}
+
+// Renders one line per row, with each cell's Display
+// (which should be a single character) side by side.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.width == 0 {
+            return Ok(());
+        }
+
+        for row in self.cells.chunks(self.width) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        return Ok(());
+    }
+}
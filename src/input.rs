@@ -0,0 +1,144 @@
+use std::env;
+use std::fs;
+use std::io::{self, prelude::*};
+use std::path::PathBuf;
+
+use crate::answers::ANSWERS_FILE;
+
+// Environment variable which overrides the default data directory.
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+// Data directory used when none is given.
+pub const DEFAULT_DATA_DIR: &str = "data";
+
+#[cfg(test)]
+mod test_source {
+    use super::*;
+
+    #[test]
+    fn data_dir_path() {
+        let source = Source::DataDir(PathBuf::from("fixtures"));
+        assert_eq!(PathBuf::from("fixtures/day7.txt"), source.path(7).unwrap());
+    }
+
+    #[test]
+    fn file_path() {
+        let source = Source::File(PathBuf::from("mine/bags.txt"));
+        assert_eq!(PathBuf::from("mine/bags.txt"), source.path(7).unwrap());
+    }
+
+    #[test]
+    fn stdin_has_no_path() {
+        assert_eq!(None, Source::Stdin.path(7));
+    }
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(Source::Stdin, Source::from_arg("-"));
+        assert_eq!(Source::File(PathBuf::from("in.txt")), Source::from_arg("in.txt"));
+    }
+
+    #[test]
+    fn answers_path() {
+        let source = Source::DataDir(PathBuf::from("fixtures"));
+        assert_eq!(PathBuf::from("fixtures/answers.txt"), source.answers_path());
+    }
+
+    #[test]
+    fn describe() {
+        assert_eq!("data/day3.txt", Source::DataDir(PathBuf::from("data")).describe(3));
+        assert_eq!("<stdin>", Source::Stdin.describe(3));
+    }
+
+    #[test]
+    fn read_data_dir() {
+        let input = Source::DataDir(PathBuf::from(DEFAULT_DATA_DIR)).read(15).expect("Could not read input");
+        assert_eq!("14,8,16,0,1,17", input.trim());
+    }
+
+    #[test]
+    fn read_missing_file() {
+        let err = Source::DataDir(PathBuf::from("no_such_dir")).read(1).expect_err("No error returned.");
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+    }
+}
+
+// Where to read a day's puzzle input from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Source {
+    // A single file, used regardless of the day.
+    File(PathBuf),
+
+    // Standard input.
+    Stdin,
+
+    // A directory containing one file per day, named dayN.txt.
+    DataDir(PathBuf)
+}
+
+impl Source {
+    // Interprets a command-line argument as a source,
+    // where "-" means standard input.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            return Source::Stdin;
+        }
+
+        return Source::File(PathBuf::from(arg));
+    }
+
+    // The path of the file the input for a given day is read from,
+    // if it comes from a file.
+    pub fn path(&self, day: u32) -> Option<PathBuf> {
+        return match self {
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin => None,
+            Source::DataDir(dir) => Some(dir.join(format!("day{}.txt", day)))
+        };
+    }
+
+    // The answer file to check inputs from this source against.
+    // This is in the data directory, or the default data directory
+    // if the input doesn't come from one.
+    pub fn answers_path(&self) -> PathBuf {
+        return match self {
+            Source::DataDir(dir) => dir.join(ANSWERS_FILE),
+            _ => default_data_dir().join(ANSWERS_FILE)
+        };
+    }
+
+    // A short description of where the input for a given day comes from.
+    pub fn describe(&self, day: u32) -> String {
+        return match self.path(day) {
+            Some(path) => path.display().to_string(),
+            None => String::from("<stdin>")
+        };
+    }
+
+    // Reads the whole input for a given day.
+    pub fn read(&self, day: u32) -> io::Result<String> {
+        return match self.path(day) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        };
+    }
+}
+
+impl Default for Source {
+    fn default() -> Source {
+        return Source::DataDir(default_data_dir());
+    }
+}
+
+// The data directory named by the environment, or the
+// default data directory if that is not set.
+pub fn default_data_dir() -> PathBuf {
+    return match env::var_os(DATA_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(DEFAULT_DATA_DIR)
+    };
+}
@@ -1 +1,27 @@
+// Explicit returns, assert_eq!(true, ...) and tests at the top
+// of each file are used throughout.
+#![allow(clippy::needless_return, clippy::bool_assert_comparison, clippy::items_after_test_module)]
+
+pub mod answers;
+pub mod bench;
+pub mod debug;
+pub mod error;
+pub mod examples;
+pub mod export;
 pub mod file;
+pub mod gen;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod parallel;
+pub mod parse;
+pub mod property;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod days;
+pub mod table;
+pub mod verify;
+pub mod viz;
+
+pub use error::{Error, Result};
@@ -0,0 +1,248 @@
+// Counting heap allocations, to see which solutions allocate the most.
+//
+// CountingAllocator wraps the system allocator, keeping count of each
+// thread's allocations. It only counts anything once it's installed
+// as the global allocator, which the aoc binary does when built with
+// the count-allocations feature:
+//
+//     cargo run --release --features count-allocations --bin aoc -- bench 15
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hint::black_box;
+
+#[cfg(test)]
+mod test_measure {
+    use super::*;
+
+    // Allocates through a CountingAllocator directly, since
+    // it isn't the global allocator in tests.
+    fn allocate(allocator: &CountingAllocator, size: usize) -> (*mut u8, Layout) {
+        let layout = Layout::from_size_align(size, 8).unwrap();
+        let ptr = unsafe { allocator.alloc(layout) };
+        assert!(!ptr.is_null());
+
+        return (ptr, layout);
+    }
+
+    #[test]
+    fn counts() {
+        let allocator = CountingAllocator;
+
+        let ((), usage) = measure(|| {
+            let (a, a_layout) = allocate(&allocator, 100);
+            let (b, b_layout) = allocate(&allocator, 50);
+
+            unsafe {
+                allocator.dealloc(a, a_layout);
+                allocator.dealloc(b, b_layout);
+            }
+
+            let (c, c_layout) = allocate(&allocator, 120);
+            unsafe { allocator.dealloc(c, c_layout) };
+        });
+
+        assert_eq!(3, usage.allocations);
+        assert_eq!(270, usage.allocated);
+        assert_eq!(150, usage.peak);
+    }
+
+    // Growing an allocation counts as allocating its new size.
+    #[test]
+    fn realloc() {
+        let allocator = CountingAllocator;
+
+        let ((), usage) = measure(|| {
+            let (a, layout) = allocate(&allocator, 16);
+
+            unsafe {
+                let a = allocator.realloc(a, layout, 64);
+                allocator.dealloc(a, Layout::from_size_align(64, 8).unwrap());
+            }
+        });
+
+        assert_eq!(2, usage.allocations);
+        assert_eq!(80, usage.allocated);
+        assert_eq!(64, usage.peak);
+    }
+
+    // The peak is measured from how much was allocated at the start,
+    // so freeing memory allocated before then makes room for more.
+    #[test]
+    fn peak_from_start() {
+        let allocator = CountingAllocator;
+        let (before, before_layout) = allocate(&allocator, 1000);
+
+        let ((), usage) = measure(|| {
+            unsafe { allocator.dealloc(before, before_layout) };
+
+            let (a, layout) = allocate(&allocator, 10);
+            unsafe { allocator.dealloc(a, layout) };
+        });
+
+        assert_eq!(1, usage.allocations);
+        assert_eq!(0, usage.peak);
+    }
+
+    #[test]
+    fn not_installed() {
+        assert_eq!(false, counting());
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!("512B", format_bytes(512));
+        assert_eq!("1.50KiB", format_bytes(1536));
+        assert_eq!("2.00MiB", format_bytes(2 * 1024 * 1024));
+        assert_eq!("3.25GiB", format_bytes(3 * 1024 * 1024 * 1024 + 256 * 1024 * 1024));
+    }
+}
+
+// A thread's allocations so far.
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    allocated: u64,
+
+    // Bytes allocated and not yet freed by this thread. Memory can be
+    // freed by a different thread to the one which allocated it, so
+    // this can go below zero.
+    current: i64,
+
+    // The most <current> has been since the counters were last reset.
+    peak: i64
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { allocations: 0, allocated: 0, current: 0, peak: 0 })
+    };
+}
+
+// Updates this thread's counters, unless the thread is being torn down.
+fn update<F: FnOnce(&mut Counters)>(f: F) {
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        f(&mut counters);
+        cell.set(counters);
+    });
+}
+
+fn allocated(counters: &mut Counters, size: usize) {
+    counters.allocations += 1;
+    counters.allocated += size as u64;
+    counters.current += size as i64;
+    counters.peak = counters.peak.max(counters.current);
+}
+
+// The system allocator, counting allocations made by each thread.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            update(|c| allocated(c, layout.size()));
+        }
+
+        return ptr;
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+
+        if !ptr.is_null() {
+            update(|c| allocated(c, layout.size()));
+        }
+
+        return ptr;
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        update(|c| c.current -= layout.size() as i64);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+
+        if !new_ptr.is_null() {
+            update(|c| {
+                c.current -= layout.size() as i64;
+                allocated(c, new_size);
+            });
+        }
+
+        return new_ptr;
+    }
+}
+
+// What a function allocated on the thread it ran on.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Usage {
+    // Number of allocations, including growing an existing one.
+    pub allocations: u64,
+
+    // Total bytes asked for by those allocations.
+    pub allocated: u64,
+
+    // Most bytes allocated and not yet freed at any one time,
+    // on top of what was already allocated when the function started.
+    pub peak: u64
+}
+
+// Runs a function, returning its result and what it allocated.
+// Only allocations made by the calling thread are counted, and
+// only if CountingAllocator is the global allocator.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Usage) {
+    let mut start = None;
+
+    update(|c| {
+        c.peak = c.current;
+        start = Some(*c);
+    });
+
+    let result = f();
+
+    let mut usage = Usage::default();
+
+    update(|c| {
+        if let Some(start) = start {
+            usage = Usage {
+                allocations: c.allocations - start.allocations,
+                allocated: c.allocated - start.allocated,
+                peak: (c.peak - start.current).max(0) as u64
+            };
+        }
+    });
+
+    return (result, usage);
+}
+
+// Whether allocations are being counted,
+// i.e. CountingAllocator is the global allocator.
+pub fn counting() -> bool {
+    let (_, usage) = measure(|| black_box(Box::new(0u64)));
+    return usage.allocations > 0;
+}
+
+// Formats a number of bytes in the largest binary unit
+// it is at least one of.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    return format!("{:.2}{}", value, UNITS[unit]);
+}
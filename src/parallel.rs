@@ -0,0 +1,159 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(test)]
+mod test_map_ordered {
+    use super::*;
+
+    // Later items finish first, but are still handled in order.
+    #[test]
+    fn in_order() {
+        let items: Vec<u64> = (0..8).collect();
+        let mut results = Vec::new();
+
+        map_ordered(&items, 4, |&i| {
+            thread::sleep(Duration::from_millis(40 - i * 5));
+            return i * 10;
+        }, |r| results.push(r));
+
+        assert_eq!(vec![0, 10, 20, 30, 40, 50, 60, 70], results);
+    }
+
+    #[test]
+    fn single_job() {
+        let items = vec!["a", "bb", "ccc"];
+        let mut results = Vec::new();
+
+        map_ordered(&items, 1, |s| s.len(), |r| results.push(r));
+
+        assert_eq!(vec![1, 2, 3], results);
+    }
+
+    #[test]
+    fn no_items() {
+        let items: Vec<u32> = Vec::new();
+        let mut count = 0;
+
+        map_ordered(&items, 4, |&i| i, |_| count += 1);
+
+        assert_eq!(0, count);
+    }
+}
+
+#[cfg(test)]
+mod test_with_timeout {
+    use super::*;
+
+    #[test]
+    fn finishes() {
+        assert_eq!(Ok(3), with_timeout(Some(Duration::from_secs(10)), || 1 + 2));
+        assert_eq!(Ok(3), with_timeout(None, || 1 + 2));
+    }
+
+    #[test]
+    fn times_out() {
+        let result = with_timeout(Some(Duration::from_millis(10)), || {
+            thread::sleep(Duration::from_secs(5));
+            return 0;
+        });
+
+        assert_eq!(Err(Unfinished::TimedOut), result);
+    }
+
+    #[test]
+    fn panics() {
+        let result: Result<u32, Unfinished> = with_timeout(None, || panic!("Expected panic"));
+        assert_eq!(Err(Unfinished::Panicked), result);
+    }
+}
+
+// The number of jobs to run at once when none is given:
+// one per CPU, if that can be found out.
+pub fn default_jobs() -> usize {
+    return thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+}
+
+// Applies a function to each item on a pool of up to <jobs> threads,
+// handing each result to <each> on the calling thread in the same
+// order as the items, as soon as it and every result before it is ready.
+pub fn map_ordered<T, R, F, E>(items: &[T], jobs: usize, f: F, mut each: E)
+    where T: Sync, R: Send, F: Fn(&T) -> R + Sync, E: FnMut(R) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1).min(items.len()) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+
+            scope.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+
+                    let item = match items.get(i) {
+                        Some(item) => item,
+                        None => return
+                    };
+
+                    // The receiver only goes away if the calling
+                    // thread has panicked, so there's no-one to tell.
+                    if sender.send((i, f(item))).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        // Only the workers' senders are left, so the loop below
+        // finishes once they all have.
+        drop(sender);
+
+        // Results which arrived before an earlier one.
+        let mut waiting: Vec<Option<R>> = (0..items.len()).map(|_| None).collect();
+        let mut handled = 0;
+
+        for (i, result) in receiver {
+            waiting[i] = Some(result);
+
+            while let Some(result) = waiting.get_mut(handled).and_then(Option::take) {
+                each(result);
+                handled += 1;
+            }
+        }
+    });
+}
+
+// Why a function run with a time limit gave no result.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Unfinished {
+    TimedOut,
+    Panicked
+}
+
+// Runs a function on its own thread, waiting at most <limit>
+// for it to finish, or forever if there is no limit.
+//
+// Threads can't be stopped from outside, so a function which
+// times out is left running in the background until it finishes
+// or the process exits.
+pub fn with_timeout<R, F>(limit: Option<Duration>, f: F) -> Result<R, Unfinished>
+    where R: Send + 'static, F: FnOnce() -> R + Send + 'static {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        // Nobody is listening any more if we timed out.
+        let _ = sender.send(f());
+    });
+
+    // If the function panics, the sender is dropped without sending.
+    return match limit {
+        Some(limit) => match receiver.recv_timeout(limit) {
+            Ok(r) => Ok(r),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(Unfinished::TimedOut),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(Unfinished::Panicked)
+        },
+        None => receiver.recv().map_err(|_| Unfinished::Panicked)
+    };
+}
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::iter::Enumerate;
+use std::str::{self, FromStr};
+
+use crate::error::{Error, Result};
+
+#[cfg(test)]
+mod test_lines {
+    use super::*;
+
+    #[test]
+    fn skips_blank_lines() {
+        let lines: Vec<(usize, &str)> = lines("\nabc\n\n  def  \n\n").map(|l| (l.number, l.text)).collect();
+        assert_eq!(vec![(2, "abc"), (4, "def")], lines);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(0, lines("").count());
+        assert_eq!(0, lines("\n \n").count());
+    }
+
+    // Errors from parsing part of a line are moved to where
+    // that part is in the untrimmed line.
+    #[test]
+    fn locate() {
+        let line = lines("\n   abc").next().unwrap();
+        let err = line.locate(Error::parse("Bad").at_column(2));
+
+        assert_eq!("Line 2, column 5: Bad", err.to_string());
+    }
+
+    #[test]
+    fn parse() {
+        let mut lines = lines("12\n  x3");
+
+        assert_eq!(12, lines.next().unwrap().parse::<u32>().unwrap());
+
+        let err = lines.next().unwrap().parse::<u32>().expect_err("No error returned.");
+        assert_eq!("Line 2, column 3: Not a number: x3", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_column_of {
+    use super::*;
+
+    #[test]
+    fn parts_of_line() {
+        let line = "light red bags contain";
+        let words: Vec<&str> = line.split(' ').collect();
+
+        assert_eq!(1, column_of(line, words[0]));
+        assert_eq!(7, column_of(line, words[1]));
+        assert_eq!(16, column_of(line, words[3]));
+    }
+
+    // Columns count bytes, not characters.
+    #[test]
+    fn multi_byte() {
+        let line = "é x";
+        assert_eq!(4, column_of(line, &line[3..]));
+    }
+}
+
+#[cfg(test)]
+mod test_numbers {
+    use super::*;
+
+    #[test]
+    fn per_line() {
+        assert_eq!(vec![1721, 979, 366], numbers::<u32>("1721\n979\n366").unwrap());
+        assert_eq!(vec![-1, 2], numbers::<i64>("-1\n+2\n").unwrap());
+    }
+
+    // Trailing newlines and blank lines aren't numbers.
+    #[test]
+    fn blank_lines() {
+        assert_eq!(vec![16, 10, 15], numbers::<u32>("16\n10\n\n15\n\n").unwrap());
+    }
+
+    #[test]
+    fn error_line() {
+        let err = numbers::<u32>("16\n10\n\nfifteen\n").expect_err("No error returned.");
+        assert_eq!("Line 4, column 1: Not a number: fifteen", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_separated {
+    use super::*;
+
+    #[test]
+    fn comma_separated_numbers() {
+        assert_eq!(vec![0, 3, 6], comma_separated::<u32>("0,3,6").unwrap());
+        assert_eq!(vec![0, 3, 6], comma_separated::<u32>(" 0, 3 ,6 ").unwrap());
+    }
+
+    #[test]
+    fn error_column() {
+        let err = comma_separated::<u32>("0,3, six").expect_err("No error returned.");
+        assert_eq!("Column 6: Not a number: six", err.to_string());
+    }
+
+    #[test]
+    fn custom_items() {
+        let items = separated("7,x,13", ',', |s| {
+            if s == "x" {
+                Ok(None)
+            } else {
+                number(s).map(Some)
+            }
+        });
+
+        assert_eq!(vec![Some(7), None, Some(13)], items.unwrap());
+    }
+
+    // An error with its own column is moved to where the item is.
+    #[test]
+    fn custom_error_column() {
+        let err = separated("ab;cd", ';', |s| {
+            if s == "cd" {
+                Err(Error::parse("Bad d").at_column(2))
+            } else {
+                Ok(s.len())
+            }
+        }).expect_err("No error returned.");
+
+        assert_eq!("Column 5: Bad d", err.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_record {
+    use super::*;
+
+    #[test]
+    fn fields() {
+        let record = record("ecl:gry pid:860033327\nhcl:#fffffd", ':').unwrap();
+
+        assert_eq!(3, record.len());
+        assert_eq!("gry", record["ecl"]);
+        assert_eq!("860033327", record["pid"]);
+        assert_eq!("#fffffd", record["hcl"]);
+    }
+
+    #[test]
+    fn duplicate_key() {
+        let err = record("ecl:gry pid:860033327\n  ecl:amb", ':').expect_err("No error returned.");
+        assert_eq!("Line 2, column 3: Duplicate key: ecl", err.to_string());
+    }
+
+    #[test]
+    fn missing_separator() {
+        let err = record("ecl:gry pid860033327", ':').expect_err("No error returned.");
+        assert_eq!("Line 1, column 9: Expected key:value, found pid860033327", err.to_string());
+
+        let err = record("ecl:gré pid860033327", ':').expect_err("No error returned.");
+        assert_eq!("Line 1, column 10: Expected key:value, found pid860033327", err.to_string());
+    }
+}
+
+// A line of input with its surrounding whitespace removed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Line<'a> {
+    // Counting from 1.
+    pub number: usize,
+
+    pub text: &'a str,
+
+    // How much whitespace was removed from the start of the line.
+    pub indent: usize
+}
+
+impl<'a> Line<'a> {
+    // Places an error found in this line's text at the right
+    // line and column of the input.
+    pub fn locate(&self, err: Error) -> Error {
+        return err.shift_column(self.indent).at_line(self.number);
+    }
+
+    // Parses the whole line as a number.
+    pub fn parse<T: FromStr>(&self) -> Result<T> {
+        return number(self.text).map_err(|e| self.locate(e));
+    }
+}
+
+// Iterator over the lines of some input which aren't blank.
+pub struct Lines<'a> {
+    lines: Enumerate<str::Lines<'a>>
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        loop {
+            let (i, line) = self.lines.next()?;
+            let text = line.trim();
+
+            if text.is_empty() {
+                continue;
+            }
+
+            return Some(Line {
+                number: i + 1,
+                text,
+                indent: line.len() - line.trim_start().len()
+            });
+        }
+    }
+}
+
+// The lines of some input which aren't blank, trimmed,
+// along with their line numbers.
+pub fn lines(input: &str) -> Lines<'_> {
+    return Lines { lines: input.lines().enumerate() };
+}
+
+// Parses a string as a number, or anything else that implements FromStr.
+pub fn number<T: FromStr>(s: &str) -> Result<T> {
+    return match s.parse() {
+        Ok(n) => Ok(n),
+        Err(_) => Err(Error::parse(format!("Not a number: {}", s)).at_column(1).with_text(s))
+    };
+}
+
+// The column that part of a line starts at, where the part is
+// a slice of the line, e.g. one of the pieces from split().
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    assert!(offset <= line.len(), "Not part of the line");
+
+    return offset + 1;
+}
+
+// Parses input with one number on each line, skipping blank lines.
+pub fn numbers<T: FromStr>(input: &str) -> Result<Vec<T>> {
+    return lines(input).map(|line| line.parse()).collect();
+}
+
+// Parses a list of items separated by a character, on one line,
+// using a function to parse each item. Items are trimmed first.
+//
+// Any error from the function is given the item's column.
+pub fn separated<T, F>(s: &str, separator: char, mut f: F) -> Result<Vec<T>>
+    where F: FnMut(&str) -> Result<T> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+
+    for item in s.split(separator) {
+        let indent = item.len() - item.trim_start().len();
+
+        match f(item.trim()) {
+            Ok(v) => items.push(v),
+            Err(e) => return Err(e.at_column(1).shift_column(offset + indent))
+        }
+
+        offset += item.len() + separator.len_utf8();
+    }
+
+    return Ok(items);
+}
+
+// Parses a comma-separated list of numbers.
+pub fn comma_separated<T: FromStr>(s: &str) -> Result<Vec<T>> {
+    return separated(s, ',', number);
+}
+
+// Parses a record of whitespace-separated fields, each a key
+// and value separated by a character, e.g. "ecl:gry pid:860033327".
+// The fields may be spread over several lines.
+//
+// It is an error for a key to appear more than once.
+pub fn record(s: &str, separator: char) -> Result<HashMap<String, String>> {
+    let mut fields = HashMap::new();
+
+    for (i, line) in s.lines().enumerate() {
+        for field in line.split(char::is_whitespace) {
+            let field_column = column_of(line, field);
+
+            if field.is_empty() {
+                continue;
+            }
+
+            let err = |message: String| {
+                return Error::parse(message).at_line(i + 1).at_column(field_column).with_text(field);
+            };
+
+            let (key, value) = match field.find(separator) {
+                Some(index) => (&field[..index], &field[index + separator.len_utf8()..]),
+                None => return Err(err(format!("Expected key{}value, found {}", separator, field)))
+            };
+
+            if fields.contains_key(key) {
+                return Err(err(format!("Duplicate key: {}", key)));
+            }
+
+            fields.insert(String::from(key), String::from(value));
+        }
+    }
+
+    return Ok(fields);
+}
@@ -0,0 +1,247 @@
+// A small property-testing harness, for checking an optimised
+// function against a simple (but slow) reference implementation.
+//
+// Cases are generated from a seeded random number generator, so a
+// failure can always be reproduced. When a case fails, it is shrunk:
+// simpler versions of it are tried for as long as one still fails,
+// and the simplest failing case found is reported.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::gen::Rng;
+
+#[cfg(test)]
+mod test_check {
+    use super::*;
+
+    #[test]
+    fn passes() {
+        let result = check(100, 1, |rng| rng.below(1000), |&n| {
+            return if n < 1000 { Ok(()) } else { Err(String::from("Too big")) };
+        });
+
+        assert_eq!(Ok(()), result);
+    }
+
+    // Numbers shrink to the smallest which still fails.
+    #[test]
+    fn shrinks_number() {
+        let failure = check(100, 1, |rng| rng.range(0, 1_000_000), |&n| {
+            return if n < 1234 { Ok(()) } else { Err(format!("{} is too big", n)) };
+        }).expect_err("No error returned.");
+
+        assert_eq!(1234, failure.shrunk);
+        assert_eq!("1234 is too big", failure.message);
+    }
+
+    // Lists shrink by losing items, and by shrinking the items left.
+    #[test]
+    fn shrinks_list() {
+        let generate = |rng: &mut Rng| (0..rng.range(0, 20)).map(|_| rng.below(100)).collect::<Vec<u64>>();
+
+        let failure = check(200, 2, generate, |list| {
+            return if list.iter().sum::<u64>() < 50 { Ok(()) } else { Err(String::from("Sum too big")) };
+        }).expect_err("No error returned.");
+
+        assert_eq!(vec![50], failure.shrunk);
+    }
+
+    // The same seed gives the same failure, from the same case.
+    #[test]
+    fn reproducible() {
+        let run = || check(100, 7, |rng| (rng.below(100), rng.below(100)), |&(a, b)| {
+            return if a + b < 150 { Ok(()) } else { Err(String::from("Sum too big")) };
+        }).expect_err("No error returned.");
+
+        let (a, b) = (run(), run());
+
+        assert_eq!(a.seed, b.seed);
+        assert_eq!(a.original, b.original);
+
+        let mut rng = Rng::new(a.seed);
+        assert_eq!(a.original, (rng.below(100), rng.below(100)));
+    }
+
+    // A panic counts as the property failing.
+    #[test]
+    fn catches_panics() {
+        let failure = check(100, 3, |rng| rng.below(10), |&n| {
+            assert!(n < 5, "Expected panic");
+            return Ok(());
+        }).expect_err("No error returned.");
+
+        assert_eq!(5, failure.shrunk);
+        assert!(failure.message.contains("Expected panic"));
+    }
+}
+
+// A value which can be made simpler, to find the simplest case
+// which makes a property fail.
+pub trait Shrink: Sized {
+    // Simpler versions of this value, the simplest first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                fn shrink(&self) -> Vec<$t> {
+                    let n = *self;
+
+                    if n == 0 {
+                        return vec![];
+                    }
+
+                    let mut simpler = vec![0, n / 2, n - 1];
+                    simpler.dedup();
+
+                    return simpler;
+                }
+            }
+        )*
+    };
+}
+
+shrink_unsigned!(u8, u16, u32, u64, usize);
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Vec<T>> {
+        let mut simpler = Vec::new();
+
+        if self.is_empty() {
+            return simpler;
+        }
+
+        // Drop the back or front half, then single items.
+        simpler.push(self[..self.len() / 2].to_vec());
+        simpler.push(self[self.len() / 2 + 1..].to_vec());
+
+        for i in 0..self.len() {
+            let mut without = self.clone();
+            without.remove(i);
+            simpler.push(without);
+        }
+
+        for (i, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut shrunk = self.clone();
+                shrunk[i] = smaller;
+                simpler.push(shrunk);
+            }
+        }
+
+        return simpler;
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<(A, B)> {
+        let first = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let second = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+
+        return first.chain(second).collect();
+    }
+}
+
+// Most attempts made to shrink a failing case, so that
+// shrinking large cases can't take forever.
+const MAX_SHRINKS: usize = 10_000;
+
+// A case for which a property didn't hold.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Failure<T> {
+    // The seed the case was generated from.
+    pub seed: u64,
+
+    // The case as it was generated, and the simplest version
+    // of it found which still fails.
+    pub original: T,
+    pub shrunk: T,
+
+    // Why the shrunk case failed.
+    pub message: String
+}
+
+impl<T: fmt::Debug> fmt::Display for Failure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Property failed: {}", self.message)?;
+        writeln!(f, "Shrunk case: {:?}", self.shrunk)?;
+        write!(f, "Original case: {:?} (case seed {})", self.original, self.seed)
+    }
+}
+
+// Checks a property against <cases> cases made by <generate>.
+// The property returns why it doesn't hold for a case, or panics.
+//
+// Each case is generated from its own seed, which is taken from
+// a generator seeded with <seed>.
+pub fn check<T, G, P>(cases: usize, seed: u64, mut generate: G, property: P) -> Result<(), Failure<T>>
+    where T: Shrink + Clone, G: FnMut(&mut Rng) -> T, P: Fn(&T) -> Result<(), String> {
+    let mut seeds = Rng::new(seed);
+
+    for _ in 0..cases {
+        let seed = seeds.next_u64();
+        let case = generate(&mut Rng::new(seed));
+
+        if let Err(message) = holds(&property, &case) {
+            let (shrunk, message) = shrink(&property, case.clone(), message);
+
+            return Err(Failure { seed, original: case, shrunk, message });
+        }
+    }
+
+    return Ok(());
+}
+
+// Checks a property, panicking with the simplest failing case if it
+// doesn't hold. For use in tests.
+pub fn assert_holds<T, G, P>(cases: usize, seed: u64, generate: G, property: P)
+    where T: Shrink + Clone + fmt::Debug, G: FnMut(&mut Rng) -> T, P: Fn(&T) -> Result<(), String> {
+    if let Err(failure) = check(cases, seed, generate, property) {
+        panic!("{}", failure);
+    }
+}
+
+// Checks a property for one case, counting a panic as a failure.
+fn holds<T, P: Fn(&T) -> Result<(), String>>(property: &P, case: &T) -> Result<(), String> {
+    return match panic::catch_unwind(AssertUnwindSafe(|| property(case))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown panic"));
+
+            Err(format!("Panicked: {}", message))
+        }
+    };
+}
+
+// Repeatedly replaces a failing case with the first simpler version
+// of it which also fails, until none do.
+fn shrink<T: Shrink, P: Fn(&T) -> Result<(), String>>(property: &P, case: T, message: String) -> (T, String) {
+    let mut case = case;
+    let mut message = message;
+    let mut attempts = 0;
+
+    'simpler: loop {
+        for simpler in case.shrink() {
+            attempts += 1;
+
+            if attempts > MAX_SHRINKS {
+                break 'simpler;
+            }
+
+            if let Err(m) = holds(property, &simpler) {
+                case = simpler;
+                message = m;
+                continue 'simpler;
+            }
+        }
+
+        break;
+    }
+
+    return (case, message);
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::solution::Part;
+
+#[cfg(test)]
+mod test_format {
+    use super::*;
+
+    fn solved() -> Record {
+        return Record {
+            day: 7,
+            part: Part::Two,
+            status: Status::Solved,
+            answer: Some(String::from("1664")),
+            error: None,
+            elapsed: Duration::from_nanos(12345),
+            input: Some(String::from("fnv:0123456789abcdef"))
+        };
+    }
+
+    fn failed() -> Record {
+        return Record {
+            day: 8,
+            part: Part::One,
+            status: Status::Failed,
+            answer: None,
+            error: Some(String::from("Line 3, column 1: Unknown opcode: \"xyz\"")),
+            elapsed: Duration::from_nanos(0),
+            input: None
+        };
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(Some(Format::Text), Format::from_name("text"));
+        assert_eq!(Some(Format::Json), Format::from_name("json"));
+        assert_eq!(Some(Format::Csv), Format::from_name("csv"));
+        assert_eq!(None, Format::from_name("xml"));
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!("Day 7, part 2: 1664", Format::Text.record(&solved()));
+        assert_eq!("Day 8, part 1: Line 3, column 1: Unknown opcode: \"xyz\"", Format::Text.record(&failed()));
+        assert_eq!(None, Format::Text.header());
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            r#"{"day":7,"part":2,"status":"solved","answer":"1664","error":null,"elapsed_ns":12345,"input":"fnv:0123456789abcdef"}"#,
+            Format::Json.record(&solved()));
+
+        assert_eq!(
+            r#"{"day":8,"part":1,"status":"failed","answer":null,"error":"Line 3, column 1: Unknown opcode: \"xyz\"","elapsed_ns":0,"input":null}"#,
+            Format::Json.record(&failed()));
+
+        // JSON lines have no header.
+        assert_eq!(None, Format::Json.header());
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(Some("day,part,status,answer,error,elapsed_ns,input"), Format::Csv.header());
+
+        assert_eq!("7,2,solved,1664,,12345,fnv:0123456789abcdef", Format::Csv.record(&solved()));
+        assert_eq!(r#"8,1,failed,,"Line 3, column 1: Unknown opcode: ""xyz""",0,"#, Format::Csv.record(&failed()));
+    }
+
+    #[test]
+    fn timed_out() {
+        let record = Record {
+            day: 13,
+            part: Part::Two,
+            status: Status::TimedOut,
+            answer: None,
+            error: Some(String::from("Timed out after 60.00s")),
+            elapsed: Duration::from_secs(60),
+            input: Some(String::from("fnv:0123456789abcdef"))
+        };
+
+        assert_eq!("13,2,timed out,,Timed out after 60.00s,60000000000,fnv:0123456789abcdef", Format::Csv.record(&record));
+    }
+
+    #[test]
+    fn json_escapes() {
+        assert_eq!(r#""a\"b\\c\nd\u0007""#, json_string("a\"b\\c\nd\u{7}"));
+    }
+
+    #[test]
+    fn csv_escapes() {
+        assert_eq!("abc", csv_field("abc"));
+        assert_eq!("\"a,b\"", csv_field("a,b"));
+        assert_eq!("\"a\"\"b\"", csv_field("a\"b"));
+        assert_eq!("\"a\nb\"", csv_field("a\nb"));
+    }
+}
+
+// How the runner prints each day and part's result.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    // A sentence per part, for people.
+    Text,
+
+    // One JSON object per line.
+    Json,
+
+    // Comma-separated values, with a header row.
+    Csv
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        return match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None
+        };
+    }
+
+    // The line to print before any records, if the format has one.
+    pub fn header(&self) -> Option<&'static str> {
+        return match self {
+            Format::Csv => Some("day,part,status,answer,error,elapsed_ns,input"),
+            _ => None
+        };
+    }
+
+    // Formats a record as a single line, without the newline.
+    pub fn record(&self, record: &Record) -> String {
+        return match self {
+            Format::Text => text(record),
+            Format::Json => json(record),
+            Format::Csv => csv(record)
+        };
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Status {
+    Solved,
+    Failed,
+
+    // Solving took longer than the time limit, and was given up on.
+    TimedOut
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::Failed => write!(f, "failed"),
+            Status::TimedOut => write!(f, "timed out")
+        }
+    }
+}
+
+// The result of running one part of one day.
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub status: Status,
+
+    // Only set if the part was solved.
+    pub answer: Option<String>,
+
+    // Why the part could not be solved, if it wasn't:
+    // the input could not be read or parsed, or solving failed.
+    pub error: Option<String>,
+
+    // Time taken to solve the part, not including parsing.
+    // Zero if it was never solved, or the time limit if it timed out.
+    pub elapsed: Duration,
+
+    // The key identifying the input in the answer file,
+    // or None if the input could not be read.
+    pub input: Option<String>
+}
+
+fn text(record: &Record) -> String {
+    let result = match (&record.answer, &record.error) {
+        (Some(answer), _) => answer.as_str(),
+        (None, Some(error)) => error.as_str(),
+        (None, None) => "-"
+    };
+
+    return format!("Day {}, part {}: {}", record.day, record.part, result);
+}
+
+fn json(record: &Record) -> String {
+    let optional = |s: &Option<String>| {
+        return match s {
+            Some(s) => json_string(s),
+            None => String::from("null")
+        };
+    };
+
+    return format!(
+        "{{\"day\":{},\"part\":{},\"status\":\"{}\",\"answer\":{},\"error\":{},\"elapsed_ns\":{},\"input\":{}}}",
+        record.day,
+        record.part,
+        record.status,
+        optional(&record.answer),
+        optional(&record.error),
+        record.elapsed.as_nanos(),
+        optional(&record.input));
+}
+
+fn csv(record: &Record) -> String {
+    let optional = |s: &Option<String>| {
+        return match s {
+            Some(s) => csv_field(s),
+            None => String::new()
+        };
+    };
+
+    return [
+        record.day.to_string(),
+        record.part.to_string(),
+        record.status.to_string(),
+        optional(&record.answer),
+        optional(&record.error),
+        record.elapsed.as_nanos().to_string(),
+        optional(&record.input)
+    ].join(",");
+}
+
+// Quotes and escapes a string as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+
+    quoted.push('"');
+    return quoted;
+}
+
+// Quotes a CSV field if it contains anything that would
+// otherwise end the field or the row.
+fn csv_field(s: &str) -> String {
+    if !s.contains([',', '"', '\n', '\r']) {
+        return String::from(s);
+    }
+
+    return format!("\"{}\"", s.replace('"', "\"\""));
+}
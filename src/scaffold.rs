@@ -0,0 +1,342 @@
+// Sets up a new day: its solution module, registered with the runner,
+// an empty input file and example, and placeholder tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::ANSWERS_FILE;
+use crate::examples::EXAMPLES_DIR;
+use crate::input::DEFAULT_DATA_DIR;
+
+#[cfg(test)]
+mod test_register {
+    use super::*;
+
+    const DAYS: &str = "pub mod day1;
+pub mod day2;
+pub mod day10;
+
+use crate::solution::{puzzle, Puzzle};
+
+pub fn all() -> Vec<Box<dyn Puzzle>> {
+    return vec![
+        puzzle::<day1::Day1>(),
+        puzzle::<day2::Day2>(),
+        puzzle::<day10::Day10>(),
+    ];
+}
+";
+
+    #[test]
+    fn in_order() {
+        let registered = register(DAYS, 3).expect("Valid day rejected");
+
+        assert!(registered.contains("pub mod day2;\npub mod day3;\npub mod day10;\n"));
+        assert!(registered.contains(
+            "        puzzle::<day2::Day2>(),\n        puzzle::<day3::Day3>(),\n        puzzle::<day10::Day10>(),\n"));
+    }
+
+    #[test]
+    fn at_end() {
+        let registered = register(DAYS, 11).expect("Valid day rejected");
+
+        assert!(registered.contains("pub mod day10;\npub mod day11;\n\nuse"));
+        assert!(registered.contains("        puzzle::<day11::Day11>(),\n    ];"));
+    }
+
+    #[test]
+    fn already_registered() {
+        let err = register(DAYS, 10).expect_err("No error returned.");
+        assert_eq!("Day 10 is already registered.", err);
+    }
+
+    #[test]
+    fn module_for_day() {
+        let module = module(16);
+
+        assert!(module.starts_with("// Advent of Code 2020\n// Day 16\n"));
+        assert!(module.contains("mod test_examples {"));
+        assert!(module.contains("mod test_puzzles {"));
+        assert!(module.contains("pub struct Day16;"));
+        assert!(module.contains("const DAY: u32 = 16;"));
+    }
+}
+
+#[cfg(test)]
+mod test_create {
+    use super::*;
+
+    use std::env;
+    use std::process;
+
+    // A copy of the parts of a crate that creating a day touches,
+    // in a directory of its own.
+    fn crate_dir(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::create_dir_all(root.join(DEFAULT_DATA_DIR)).unwrap();
+        fs::create_dir_all(root.join(EXAMPLES_DIR)).unwrap();
+
+        fs::write(root.join(DAYS_MODULE), "pub mod day1;\n\npub fn all() -> Vec<Box<dyn Puzzle>> {\n    return vec![\n        puzzle::<day1::Day1>(),\n    ];\n}\n").unwrap();
+        fs::write(root.join(DEFAULT_DATA_DIR).join(ANSWERS_FILE), "1   day1.txt  1  2\n").unwrap();
+        fs::write(root.join(EXAMPLES_DIR).join(ANSWERS_FILE), "1   example1.txt  1  2").unwrap();
+
+        return root;
+    }
+
+    #[test]
+    fn creates_files() {
+        let root = crate_dir("creates");
+
+        let created = create(&root, 2).expect("Day not created");
+
+        assert_eq!(vec![
+            root.join("src/days/day2.rs"),
+            root.join("data/day2.txt"),
+            root.join("examples/day2/example1.txt")
+        ], created);
+
+        assert_eq!(module(2), fs::read_to_string(root.join("src/days/day2.rs")).unwrap());
+        assert!(fs::read_to_string(root.join(DAYS_MODULE)).unwrap().contains("pub mod day2;"));
+
+        assert_eq!("1   day1.txt  1  2\n2   day2.txt  -  -\n",
+            fs::read_to_string(root.join("data/answers.txt")).unwrap());
+
+        // A missing newline at the end of the file is added.
+        assert_eq!("1   example1.txt  1  2\n2   example1.txt  -  -\n",
+            fs::read_to_string(root.join("examples/answers.txt")).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    // An input that has already been downloaded is kept.
+    #[test]
+    fn keeps_input() {
+        let root = crate_dir("keeps");
+        fs::write(root.join("data/day2.txt"), "1-3 a: abcde\n").unwrap();
+
+        let created = create(&root, 2).expect("Day not created");
+
+        assert!(!created.contains(&root.join("data/day2.txt")));
+        assert_eq!("1-3 a: abcde\n", fs::read_to_string(root.join("data/day2.txt")).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn existing_day() {
+        let root = crate_dir("existing");
+
+        let err = create(&root, 1).expect_err("No error returned.");
+        assert_eq!("Day 1 is already registered.", err);
+
+        fs::write(root.join("src/days/day3.rs"), "").unwrap();
+
+        let err = create(&root, 3).expect_err("No error returned.");
+        assert_eq!(format!("{} already exists.", root.join("src/days/day3.rs").display()), err);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
+
+// Where the days are registered, relative to the crate root.
+const DAYS_MODULE: &str = "src/days/mod.rs";
+
+// Creates everything needed for a new day in the crate at <root>,
+// returning the files created. Nothing is changed if the day
+// already exists.
+pub fn create(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let module_path = root.join(format!("src/days/day{}.rs", day));
+
+    if module_path.exists() {
+        return Err(format!("{} already exists.", module_path.display()));
+    }
+
+    let days_path = root.join(DAYS_MODULE);
+    let registered = register(&read(&days_path)?, day)?;
+
+    write(&module_path, &module(day))?;
+    write(&days_path, &registered)?;
+
+    let mut created = vec![module_path];
+
+    let data_dir = root.join(DEFAULT_DATA_DIR);
+    let input_path = data_dir.join(format!("day{}.txt", day));
+
+    if !input_path.exists() {
+        write(&input_path, "")?;
+        created.push(input_path);
+    }
+
+    add_answers(&data_dir.join(ANSWERS_FILE), day, &format!("day{}.txt", day))?;
+
+    let example_dir = root.join(EXAMPLES_DIR).join(format!("day{}", day));
+    let example_path = example_dir.join("example1.txt");
+
+    fs::create_dir_all(&example_dir).map_err(|e| format!("Could not create {}: {}", example_dir.display(), e))?;
+    write(&example_path, "")?;
+    created.push(example_path);
+
+    add_answers(&root.join(EXAMPLES_DIR).join(ANSWERS_FILE), day, "example1.txt")?;
+
+    return Ok(created);
+}
+
+// Adds a day to the days module: its module declaration and
+// its entry in the list of puzzles, both in day order.
+pub fn register(days: &str, day: u32) -> Result<String, String> {
+    let lines: Vec<&str> = days.lines().collect();
+
+    let declaration = |line: &str| line.strip_prefix("pub mod day")
+        .and_then(|l| l.strip_suffix(';'))
+        .and_then(|d| d.parse::<u32>().ok());
+
+    let entry = |line: &str| line.trim().strip_prefix("puzzle::<day")
+        .and_then(|l| l.split("::").next())
+        .and_then(|d| d.parse::<u32>().ok());
+
+    if lines.iter().any(|&l| declaration(l) == Some(day)) {
+        return Err(format!("Day {} is already registered.", day));
+    }
+
+    let declaration_at = insert_at(&lines, declaration, day).ok_or("No day modules found.")?;
+    let entry_at = insert_at(&lines, entry, day).ok_or("No list of puzzles found.")?;
+
+    let mut registered = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if i == declaration_at {
+            registered.push(format!("pub mod day{};", day));
+        }
+
+        if i == entry_at {
+            registered.push(format!("        puzzle::<day{}::Day{}>(),", day, day));
+        }
+
+        registered.push(line.to_string());
+    }
+
+    return Ok(registered.join("\n") + "\n");
+}
+
+// The index of the line to insert a day before, so that it is
+// in order among the lines <day_of> finds a day in.
+fn insert_at<F: Fn(&str) -> Option<u32>>(lines: &[&str], day_of: F, day: u32) -> Option<usize> {
+    let mut after = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        match day_of(line) {
+            Some(d) if d > day => return Some(i),
+            Some(_) => after = Some(i + 1),
+            None => ()
+        }
+    }
+
+    return after;
+}
+
+// A new day's module, which parses the input into lines
+// and has placeholders for both parts and their examples.
+pub fn module(day: u32) -> String {
+    return MODULE_TEMPLATE.replace("{day}", &day.to_string());
+}
+
+const MODULE_TEMPLATE: &str = r#"// Advent of Code 2020
+// Day {day}
+
+use crate::error::{Error, Result};
+use crate::parse;
+use crate::solution::Solution;
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+
+    // The example from part 1 of the puzzle.
+    #[test]
+    #[ignore = "Example not filled in yet"]
+    fn test_example_part1() {
+        let input = "";
+
+        let parsed = Day{day}::parse(input).expect("Valid input rejected");
+        assert_eq!(0, Day{day}::part1(&parsed).expect("Error solving part 1"));
+    }
+
+    // The example from part 2 of the puzzle.
+    #[test]
+    #[ignore = "Example not filled in yet"]
+    fn test_example_part2() {
+        let input = "";
+
+        let parsed = Day{day}::parse(input).expect("Valid input rejected");
+        assert_eq!(0, Day{day}::part2(&parsed).expect("Error solving part 2"));
+    }
+}
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+
+    use crate::answers::Answers;
+    use crate::input::{self, Source};
+    use crate::solution::{puzzle, Part};
+    use crate::verify::{self, Status};
+
+    // The answers registered for the puzzle input should be correct.
+    #[test]
+    #[ignore = "Puzzle not solved yet"]
+    fn test_puzzle() {
+        let source = Source::DataDir(input::default_data_dir());
+        let answers = Answers::load(&source.answers_path()).unwrap();
+
+        for outcome in verify::verify(puzzle::<Day{day}>().as_ref(), &source, &answers, &Part::both()).unwrap() {
+            assert_eq!(Status::Pass, outcome.status, "Part {}", outcome.part);
+        }
+    }
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u32 = {day};
+
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        return Ok(parse::lines(input).map(|l| String::from(l.text)).collect());
+    }
+
+    fn part1(_input: &Vec<String>) -> Result<u64> {
+        return Err(Error::NoSolution(String::from("Part 1 not solved yet")));
+    }
+
+    fn part2(_input: &Vec<String>) -> Result<u64> {
+        return Err(Error::NoSolution(String::from("Part 2 not solved yet")));
+    }
+}
+"#;
+
+// Adds a line for a day with no known answers to an answer file.
+fn add_answers(path: &Path, day: u32, name: &str) -> Result<(), String> {
+    let mut answers = read(path)?;
+
+    if !answers.is_empty() && !answers.ends_with('\n') {
+        answers.push('\n');
+    }
+
+    answers.push_str(&format!("{:<3} {}  -  -\n", day, name));
+
+    return write(path, &answers);
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    return fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e));
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    return fs::write(path, contents).map_err(|e| format!("Could not write {}: {}", path.display(), e));
+}
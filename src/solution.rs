@@ -0,0 +1,119 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::debug::Simulation;
+use crate::error::{Error, Result};
+use crate::viz::Renderer;
+
+// One of the two parts of a day's puzzle.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub fn from_number(n: u32) -> Option<Part> {
+        return match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None
+        };
+    }
+
+    pub fn both() -> [Part; 2] {
+        return [Part::One, Part::Two];
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2")
+        }
+    }
+}
+
+// A solution to a single day's puzzle.
+//
+// Parsing the input is kept separate from solving each part,
+// so that the input only has to be parsed once when solving both.
+pub trait Solution {
+    // The day this is a solution for.
+    const DAY: u32;
+
+    // The parsed puzzle input, shared by both parts.
+    // Parts may be solved on other threads, so it must be
+    // safe to share between them.
+    type Input: Send + Sync;
+
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    // Draws each step of solving a part. Only some days can be
+    // visualised; the rest give an error.
+    fn visualise(_input: &Self::Input, _part: Part, _renderer: &mut dyn Renderer) -> Result<()> {
+        return Err(Error::InvalidState(format!("Day {} can't be visualised", Self::DAY)));
+    }
+
+    // A simulation of solving a part, to step through a tick at a time.
+    // Only some days can be stepped through; the rest give an error.
+    fn simulate(_input: &Self::Input, _part: Part) -> Result<Box<dyn Simulation + '_>> {
+        return Err(Error::InvalidState(format!("Day {} can't be stepped through", Self::DAY)));
+    }
+}
+
+// A type-erased Solution, so that the runner can hold
+// every day in a single list.
+pub trait Puzzle {
+    fn day(&self) -> u32;
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>>;
+}
+
+// A type-erased parsed input, able to solve either part.
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Result<String>;
+    fn visualise(&self, part: Part, renderer: &mut dyn Renderer) -> Result<()>;
+    fn simulate(&self, part: Part) -> Result<Box<dyn Simulation + '_>>;
+}
+
+struct ErasedSolution<S>(PhantomData<S>);
+
+struct ErasedInput<S: Solution>(S::Input);
+
+impl<S: Solution + 'static> Puzzle for ErasedSolution<S> {
+    fn day(&self) -> u32 {
+        return S::DAY;
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>> {
+        return Ok(Box::new(ErasedInput::<S>(S::parse(input)?)));
+    }
+}
+
+impl<S: Solution> Parsed for ErasedInput<S> {
+    fn solve(&self, part: Part) -> Result<String> {
+        return match part {
+            Part::One => S::part1(&self.0).map(|a| a.to_string()),
+            Part::Two => S::part2(&self.0).map(|a| a.to_string())
+        };
+    }
+
+    fn visualise(&self, part: Part, renderer: &mut dyn Renderer) -> Result<()> {
+        return S::visualise(&self.0, part, renderer);
+    }
+
+    fn simulate(&self, part: Part) -> Result<Box<dyn Simulation + '_>> {
+        return S::simulate(&self.0, part);
+    }
+}
+
+// Wraps a Solution up as a Puzzle.
+pub fn puzzle<S: Solution + 'static>() -> Box<dyn Puzzle> {
+    return Box::new(ErasedSolution::<S>(PhantomData));
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod test_render {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        return cells.iter().map(|c| String::from(*c)).collect();
+    }
+
+    #[test]
+    fn widths_and_alignment() {
+        let columns = [("Day", Align::Right), ("Name", Align::Left), ("Note", Align::Left)];
+        let rows = [row(&["1", "a", "x"]), row(&["12", "longer", "y"])];
+
+        assert_eq!("\
+Day  Name    Note
+  1  a       x
+ 12  longer  y
+", render(&columns, &rows));
+    }
+
+    // Trailing spaces are never left at the end of a line.
+    #[test]
+    fn no_trailing_whitespace() {
+        let columns = [("A", Align::Left), ("B", Align::Left)];
+        let rows = [row(&["long cell", ""])];
+
+        assert_eq!("A          B\nlong cell\n", render(&columns, &rows));
+    }
+
+    #[test]
+    fn no_rows() {
+        let columns = [("A", Align::Left), ("B", Align::Right)];
+        assert_eq!("A  B\n", render(&columns, &[]));
+    }
+}
+
+pub enum Align {
+    Left,
+    Right
+}
+
+// Renders rows of cells as a plain-text table with a heading row,
+// where each column is as wide as its widest cell.
+pub fn render(columns: &[(&str, Align)], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = columns.iter().map(|(h, _)| h.len()).collect();
+
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+
+    let mut push_row = |cells: Vec<&str>| {
+        let mut line = String::new();
+
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+
+            match columns[i].1 {
+                Align::Left => line.push_str(&format!("{:<width$}", cell, width = widths[i])),
+                Align::Right => line.push_str(&format!("{:>width$}", cell, width = widths[i]))
+            }
+        }
+
+        table.push_str(line.trim_end());
+        table.push('\n');
+    };
+
+    push_row(columns.iter().map(|(h, _)| *h).collect());
+
+    for row in rows {
+        push_row(row.iter().map(|c| c.as_str()).collect());
+    }
+
+    return table;
+}
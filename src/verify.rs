@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::answers::{self, Answers};
+use crate::error::Result;
+use crate::input::Source;
+use crate::solution::{Part, Puzzle};
+use crate::table::{self, Align};
+
+#[cfg(test)]
+mod test_verify {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::days;
+    use crate::input::DEFAULT_DATA_DIR;
+
+    fn outcome(status: Status) -> Outcome {
+        return Outcome {
+            day: 7,
+            part: Part::Two,
+            status,
+            answer: Some(String::from("1664")),
+            expected: Some(String::from("1664")),
+            input: String::from("fnv:0123456789abcdef")
+        };
+    }
+
+    #[test]
+    fn pass() {
+        let answers = Answers::parse("7 day7.txt 208 1664").unwrap();
+        let source = Source::DataDir(PathBuf::from(DEFAULT_DATA_DIR));
+
+        let outcomes = verify(days::get(7).unwrap().as_ref(), &source, &answers, &Part::both()).unwrap();
+
+        assert_eq!(2, outcomes.len());
+        assert_eq!(Status::Pass, outcomes[0].status);
+        assert_eq!(Status::Pass, outcomes[1].status);
+    }
+
+    #[test]
+    fn fail() {
+        let answers = Answers::parse("7 day7.txt 208 1665").unwrap();
+        let source = Source::DataDir(PathBuf::from(DEFAULT_DATA_DIR));
+
+        let outcomes = verify(days::get(7).unwrap().as_ref(), &source, &answers, &[Part::Two]).unwrap();
+
+        assert_eq!(Status::Fail, outcomes[0].status);
+        assert_eq!(Some(String::from("1664")), outcomes[0].answer);
+        assert_eq!(Some(String::from("1665")), outcomes[0].expected);
+    }
+
+    // Parts without a known answer are not run.
+    #[test]
+    fn missing() {
+        let answers = Answers::parse("7 day7.txt 208 -").unwrap();
+        let source = Source::DataDir(PathBuf::from(DEFAULT_DATA_DIR));
+
+        let outcomes = verify(days::get(7).unwrap().as_ref(), &source, &answers, &[Part::Two]).unwrap();
+
+        assert_eq!(Status::Missing, outcomes[0].status);
+        assert_eq!(None, outcomes[0].answer);
+    }
+
+    #[test]
+    fn table_format() {
+        let mut failed = outcome(Status::Fail);
+        failed.answer = Some(String::from("1"));
+
+        let mut missing = outcome(Status::Missing);
+        missing.answer = None;
+        missing.expected = None;
+
+        let table = table(&[outcome(Status::Pass), failed, missing]);
+
+        assert_eq!("\
+Day  Part  Status   Answer  Expected  Input
+  7     2  pass     1664    1664      fnv:0123456789abcdef
+  7     2  FAIL     1       1664      fnv:0123456789abcdef
+  7     2  missing  -       -         fnv:0123456789abcdef
+", table);
+    }
+
+    // Every answer registered for the inputs in the data directory
+    // should still be correct. This solves every puzzle, which is
+    // slow, so run it with --ignored.
+    #[test]
+    #[ignore = "Solves every puzzle"]
+    fn registered_answers() {
+        let source = Source::DataDir(PathBuf::from(DEFAULT_DATA_DIR));
+        let answers = Answers::load(&source.answers_path()).unwrap();
+
+        for puzzle in days::all() {
+            for outcome in verify(puzzle.as_ref(), &source, &answers, &Part::both()).unwrap() {
+                assert_ne!(Status::Fail, outcome.status, "Day {} part {}", outcome.day, outcome.part);
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Failures are capitalised so they stand out in the table.
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing")
+        }
+    }
+}
+
+// The result of checking one part of one day against its known answer.
+pub struct Outcome {
+    pub day: u32,
+    pub part: Part,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+
+    // The key identifying the input in the answer file.
+    pub input: String
+}
+
+// Solves the given parts of a day's puzzle and checks them against
+// the known answers. Parts with no known answer are not solved.
+pub fn verify(puzzle: &dyn Puzzle, source: &Source, answers: &Answers, parts: &[Part]) -> Result<Vec<Outcome>> {
+    let day = puzzle.day();
+    let input = source.read(day)?;
+
+    let path = source.path(day);
+    let name = path.as_ref()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str());
+
+    let parsed = puzzle.parse(&input)?;
+
+    let mut outcomes = Vec::new();
+
+    for &part in parts {
+        let expected = answers.expected(day, name, &input, part).map(String::from);
+
+        let (status, answer) = match &expected {
+            Some(e) => {
+                let answer = parsed.solve(part)?;
+                let status = if answer == *e { Status::Pass } else { Status::Fail };
+                (status, Some(answer))
+            },
+            None => (Status::Missing, None)
+        };
+
+        outcomes.push(Outcome {
+            day,
+            part,
+            status,
+            answer,
+            expected,
+            input: answers::key(&input)
+        });
+    }
+
+    return Ok(outcomes);
+}
+
+// Formats outcomes as a table, one row per outcome.
+pub fn table(outcomes: &[Outcome]) -> String {
+    let columns = [
+        ("Day", Align::Right),
+        ("Part", Align::Right),
+        ("Status", Align::Left),
+        ("Answer", Align::Left),
+        ("Expected", Align::Left),
+        ("Input", Align::Left)
+    ];
+
+    let mut rows = Vec::new();
+
+    for o in outcomes {
+        rows.push(vec![
+            o.day.to_string(),
+            o.part.to_string(),
+            o.status.to_string(),
+            o.answer.clone().unwrap_or_else(|| String::from("-")),
+            o.expected.clone().unwrap_or_else(|| String::from("-")),
+            o.input.clone()
+        ]);
+    }
+
+    return table::render(&columns, &rows);
+}
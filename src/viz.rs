@@ -0,0 +1,363 @@
+// Visualising how a solution gets to its answer, as a series of
+// frames of characters.
+//
+// Solutions which can be visualised draw a Frame at each step and
+// hand it to a Renderer. The Terminal renderer draws frames in place,
+// showing as much of each frame as fits on screen; the Headless one
+// just keeps them, for tests or for saving to files later.
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::grid::Grid;
+
+#[cfg(test)]
+mod test_frame {
+    use super::*;
+
+    #[test]
+    fn from_grid() {
+        let grid = Grid::parse("#.\n.#", |c| Some(c == '#')).unwrap();
+        let frame = Frame::from_grid(&grid.map(|_, &tree| if tree { 'T' } else { ' ' }));
+
+        assert_eq!(2, frame.width());
+        assert_eq!(2, frame.height());
+        assert_eq!("T \n T\n", frame.to_string());
+    }
+
+    #[test]
+    fn set_outside() {
+        let mut frame = Frame::new(3, 1);
+
+        frame.set(1, 0, '@');
+        frame.set(5, 0, '@');
+
+        assert_eq!(" @ \n", frame.to_string());
+        assert_eq!(Some('@'), frame.get(1, 0));
+        assert_eq!(None, frame.get(5, 0));
+    }
+}
+
+#[cfg(test)]
+mod test_viewport {
+    use super::*;
+
+    #[test]
+    fn whole_frame() {
+        let frame = Frame::new(10, 5);
+        assert_eq!((0, 0, 10, 5), Viewport::new(80, 24).window(&frame));
+    }
+
+    // Without a focus, the top left of the frame is shown.
+    #[test]
+    fn top_left() {
+        let frame = Frame::new(100, 50);
+        assert_eq!((0, 0, 80, 24), Viewport::new(80, 24).window(&frame));
+    }
+
+    // The focus is kept in the middle, unless that would go
+    // past an edge of the frame.
+    #[test]
+    fn follows_focus() {
+        let viewport = Viewport::new(10, 4);
+
+        assert_eq!((45, 18, 10, 4), viewport.window(&Frame::new(100, 50).with_focus(50, 20)));
+        assert_eq!((0, 0, 10, 4), viewport.window(&Frame::new(100, 50).with_focus(2, 1)));
+        assert_eq!((90, 46, 10, 4), viewport.window(&Frame::new(100, 50).with_focus(99, 49)));
+    }
+}
+
+#[cfg(test)]
+mod test_renderers {
+    use super::*;
+
+    #[test]
+    fn terminal() {
+        let mut out = Vec::new();
+
+        {
+            let mut terminal = Terminal::new(&mut out, Viewport::new(3, 2)).fps(None);
+
+            let mut frame = Frame::new(4, 3).with_caption("Step 1");
+            frame.set(0, 0, '#');
+            frame.set(3, 2, '#');
+
+            terminal.render(&frame).unwrap();
+        }
+
+        // One line of the viewport is taken up by the caption.
+        assert_eq!("\x1b[2J\x1b[1;1HStep 1\n#  \n", String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn fps_limit() {
+        let mut out = Vec::new();
+        let mut terminal = Terminal::new(&mut out, Viewport::new(3, 2)).fps(Some(50.0));
+
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            terminal.render(&Frame::new(1, 1)).unwrap();
+        }
+
+        // The first frame is drawn straight away, the others 20ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn headless() {
+        let mut headless = Headless::new();
+
+        headless.render(&Frame::new(1, 1).with_caption("a")).unwrap();
+        headless.render(&Frame::new(1, 1).with_caption("b")).unwrap();
+
+        let captions: Vec<&str> = headless.frames.iter().map(|f| f.caption()).collect();
+        assert_eq!(vec!["a", "b"], captions);
+    }
+}
+
+// A picture of a solution's state at one step: a rectangle of characters,
+// with a caption, and optionally a point of interest to keep in view.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+    focus: Option<(usize, usize)>,
+    caption: String
+}
+
+impl Frame {
+    // A blank frame.
+    pub fn new(width: usize, height: usize) -> Frame {
+        return Frame {
+            width,
+            height,
+            cells: vec![' '; width * height],
+            focus: None,
+            caption: String::new()
+        };
+    }
+
+    // A frame showing a grid, with each cell drawn as the
+    // first character of its Display.
+    pub fn from_grid<T: fmt::Display>(grid: &Grid<T>) -> Frame {
+        let mut frame = Frame::new(grid.width(), grid.height());
+
+        for ((x, y), cell) in grid.iter() {
+            frame.set(x, y, cell.to_string().chars().next().unwrap_or(' '));
+        }
+
+        return frame;
+    }
+
+    pub fn with_focus(mut self, x: usize, y: usize) -> Frame {
+        self.focus = Some((x, y));
+        return self;
+    }
+
+    pub fn with_caption<S: Into<String>>(mut self, caption: S) -> Frame {
+        self.caption = caption.into();
+        return self;
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn focus(&self) -> Option<(usize, usize)> {
+        return self.focus;
+    }
+
+    pub fn caption(&self) -> &str {
+        return &self.caption;
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        return Some(self.cells[y * self.width + x]);
+    }
+
+    // Draws a character, if the position is inside the frame.
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = c;
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[char] {
+        return &self.cells[y * self.width..(y + 1) * self.width];
+    }
+}
+
+// Renders one line per row, without the caption.
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            writeln!(f, "{}", self.row(y).iter().collect::<String>())?;
+        }
+
+        return Ok(());
+    }
+}
+
+// Something which shows frames, one after another.
+pub trait Renderer {
+    fn render(&mut self, frame: &Frame) -> io::Result<()>;
+
+    // Called after the last frame, for renderers which have
+    // anything left to do, such as finishing a file.
+    fn finish(&mut self) -> io::Result<()> {
+        return Ok(());
+    }
+}
+
+// The part of the screen a frame is drawn in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Viewport {
+    pub width: usize,
+    pub height: usize
+}
+
+impl Viewport {
+    pub fn new(width: usize, height: usize) -> Viewport {
+        return Viewport { width: width.max(1), height: height.max(1) };
+    }
+
+    // The size of the terminal, from $COLUMNS and $LINES if they're set,
+    // otherwise by asking stty, otherwise 80x24.
+    pub fn terminal() -> Viewport {
+        let from_env = |name| env::var(name).ok().and_then(|v| v.parse::<usize>().ok());
+
+        if let (Some(width), Some(height)) = (from_env("COLUMNS"), from_env("LINES")) {
+            return Viewport::new(width, height);
+        }
+
+        if let Some((width, height)) = stty_size() {
+            return Viewport::new(width, height);
+        }
+
+        return Viewport::new(80, 24);
+    }
+
+    // The part of a frame which fits in the viewport, as
+    // (left, top, width, height): centred on the frame's focus if it
+    // has one, but without going past its edges, or else its top left.
+    pub fn window(&self, frame: &Frame) -> (usize, usize, usize, usize) {
+        let width = self.width.min(frame.width());
+        let height = self.height.min(frame.height());
+
+        let (x, y) = frame.focus().unwrap_or((0, 0));
+
+        let start = |focus: usize, size: usize, total: usize| {
+            return focus.saturating_sub(size / 2).min(total - size);
+        };
+
+        return (start(x, width, frame.width()), start(y, height, frame.height()), width, height);
+    }
+}
+
+// Asks stty for the size of the controlling terminal.
+fn stty_size() -> Option<(usize, usize)> {
+    let tty = File::open("/dev/tty").ok()?;
+    let output = Command::new("stty").arg("size").stdin(tty).output().ok()?;
+
+    let size = String::from_utf8(output.stdout).ok()?;
+    let mut parts = size.split_whitespace().map(|n| n.parse::<usize>().ok());
+
+    let rows = parts.next()??;
+    let columns = parts.next()??;
+
+    return Some((columns, rows));
+}
+
+// Frames drawn per second when none is given.
+pub const DEFAULT_FPS: f64 = 10.0;
+
+// Draws each frame in place on a terminal, clearing what was there,
+// waiting between frames so they can be followed.
+pub struct Terminal<W: Write> {
+    out: W,
+    viewport: Viewport,
+    interval: Option<Duration>,
+    last: Option<Instant>
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W, viewport: Viewport) -> Terminal<W> {
+        return Terminal {
+            out,
+            viewport,
+            interval: Some(Duration::from_secs_f64(1.0 / DEFAULT_FPS)),
+            last: None
+        };
+    }
+
+    // The most frames to draw per second, or None to draw
+    // them as fast as they come.
+    pub fn fps(mut self, limit: Option<f64>) -> Terminal<W> {
+        self.interval = limit.filter(|&fps| fps > 0.0).map(|fps| Duration::from_secs_f64(1.0 / fps));
+        return self;
+    }
+}
+
+impl<W: Write> Renderer for Terminal<W> {
+    fn render(&mut self, frame: &Frame) -> io::Result<()> {
+        if let (Some(interval), Some(last)) = (self.interval, self.last) {
+            let elapsed = last.elapsed();
+
+            if elapsed < interval {
+                thread::sleep(interval - elapsed);
+            }
+        }
+
+        self.last = Some(Instant::now());
+
+        // Leave a line for the caption.
+        let viewport = Viewport::new(self.viewport.width, self.viewport.height - 1);
+        let (left, top, width, height) = viewport.window(frame);
+
+        let mut screen = String::from("\x1b[2J\x1b[1;1H");
+        screen.push_str(frame.caption());
+        screen.push('\n');
+
+        for y in top..top + height {
+            screen.extend(&frame.row(y)[left..left + width]);
+            screen.push('\n');
+        }
+
+        self.out.write_all(screen.as_bytes())?;
+        return self.out.flush();
+    }
+}
+
+// Keeps every frame without showing any of them.
+#[derive(Default)]
+pub struct Headless {
+    pub frames: Vec<Frame>
+}
+
+impl Headless {
+    pub fn new() -> Headless {
+        return Headless { frames: Vec::new() };
+    }
+}
+
+impl Renderer for Headless {
+    fn render(&mut self, frame: &Frame) -> io::Result<()> {
+        self.frames.push(frame.clone());
+        return Ok(());
+    }
+}